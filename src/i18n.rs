@@ -0,0 +1,249 @@
+// textes de l'interface, en français (langue par défaut) et en anglais
+// les messages pas encore repris ici restent écrits directement en français dans le code
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    Fr,
+    En,
+}
+
+impl Language {
+    // "fr", "en", ou une valeur de LANG comme "en_US.UTF-8"; None pour une langue non traduite (ou "C")
+    pub fn from_code(code: &str) -> Option<Language> {
+        let code = code.trim().to_lowercase();
+        if code.starts_with("fr") {
+            Some(Language::Fr)
+        } else if code.starts_with("en") {
+            Some(Language::En)
+        } else {
+            None
+        }
+    }
+}
+
+// clé, français, anglais; "{}" marque la place des valeurs passées à tr_args
+const MESSAGES: &[(&str, &str, &str)] = &[
+    ("common.yes_no", "(oui/non)", "(yes/no)"),
+    ("common.invalid_number", "Veuillez entrer un nombre valide.", "Please enter a valid number."),
+    ("common.invalid_choice", "Choix invalide.", "Invalid choice."),
+    ("common.choose_1_to_4", "Choix invalide. Veuillez entrer 1, 2, 3 ou 4.", "Invalid choice. Please enter 1, 2, 3 or 4."),
+    ("common.read_error", "Erreur de lecture: {}", "Read error: {}"),
+    ("common.goodbye", "Au revoir!", "Goodbye!"),
+    ("common.list_empty", "La liste est vide!", "The list is empty!"),
+    ("common.item_not_found", "Élément non trouvé!", "Item not found!"),
+    ("status.todo", "À faire", "To do"),
+    ("status.in_progress", "En cours", "In progress"),
+    ("status.waiting", "En attente", "Waiting"),
+    ("status.done", "Terminée", "Done"),
+    ("priority.low", "Basse", "Low"),
+    ("priority.medium", "Moyenne", "Medium"),
+    ("priority.high", "Haute", "High"),
+    ("priority.critical", "Critique", "Critical"),
+    ("recurrence.daily", "Quotidienne", "Daily"),
+    ("recurrence.weekly", "Hebdomadaire", "Weekly"),
+    ("recurrence.monthly", "Mensuelle", "Monthly"),
+    ("color.red", "Rouge", "Red"),
+    ("color.green", "Vert", "Green"),
+    ("color.yellow", "Jaune", "Yellow"),
+    ("color.blue", "Bleu", "Blue"),
+    ("color.magenta", "Magenta", "Magenta"),
+    ("color.cyan", "Cyan", "Cyan"),
+    ("sort.priority", "Priorité puis échéance", "Priority, then due date"),
+    ("sort.due_date", "Date d'échéance", "Due date"),
+    ("sort.id", "ID", "ID"),
+    ("sort.created_at", "Date de création", "Creation date"),
+    ("sort.status", "Non terminés en premier", "Pending first"),
+    ("sort.title", "Titre (alphabétique)", "Title (alphabetical)"),
+    ("sort.manual", "Ordre manuel", "Manual order"),
+    ("filter.all", "Tous les éléments", "All items"),
+    ("filter.completed", "Éléments terminés", "Completed items"),
+    ("filter.incomplete", "Éléments restants", "Remaining items"),
+    ("view.sort", "tri: {}", "sort: {}"),
+    ("view.grouped", "par catégorie", "by category"),
+    ("history.add", "Ajout", "Added"),
+    ("history.complete", "Terminé", "Completed"),
+    ("history.reopen", "Rouvert", "Reopened"),
+    ("history.edit", "Modification", "Edited"),
+    ("history.archive", "Archivage", "Archived"),
+    ("history.remove", "Suppression", "Removed"),
+    ("priority.prompt", "Choisissez la priorité (Entrée = {}):", "Choose the priority (Enter = {}):"),
+    ("item.description", "Description", "Description"),
+    ("item.follows", "↳ suite de #{}", "↳ follow-up of #{}"),
+    ("item.overdue", "⚠️  EN RETARD - échéance dépassée: {}", "⚠️  OVERDUE - due date passed: {}"),
+    ("item.due", "📅 Échéance: {}", "📅 Due: {}"),
+    ("item.due_today", "📅 Échéance: {} (AUJOURD'HUI!)", "📅 Due: {} (TODAY!)"),
+    ("item.due_tomorrow", "📅 Échéance: {} (DEMAIN)", "📅 Due: {} (TOMORROW)"),
+    ("item.due_in_days", "📅 Échéance: {} (dans {} jours)", "📅 Due: {} (in {} days)"),
+    ("item.recurrence", "🔁 Récurrence: {}", "🔁 Repeats: {}"),
+    ("item.completed_at", "✅ Terminé le: {} ({})", "✅ Completed: {} ({})"),
+    ("item.estimate_actual", "⏱️  Estimé: {} / réel: {}", "⏱️  Estimated: {} / actual: {}"),
+    ("item.estimate", "⏱️  Estimé: {}", "⏱️  Estimated: {}"),
+    ("item.actual", "⏱️  Durée réelle: {}", "⏱️  Actual time: {}"),
+    ("list.created", "Créée le: {} ({})", "Created: {} ({})"),
+    ("list.modified", "Dernière modification: {} ({})", "Last modified: {} ({})"),
+    ("list.count", "Nombre d'éléments: {}", "Items: {}"),
+    ("list.progress", "Progression: {}/{} ({}%) {}", "Progress: {}/{} ({}%) {}"),
+    ("list.points", "Points restants: {}", "Remaining points: {}"),
+    ("list.shown", "Éléments affichés: {}/{}", "Items shown: {}/{}"),
+    ("list.saved_view", "Vue enregistrée: {}", "Saved view: {}"),
+    ("list.no_items", "Aucun élément dans cette liste.", "No items in this list."),
+    ("list.by_category", "{} - par catégorie", "{} - by category"),
+    ("list.uncategorized", "Sans catégorie", "Uncategorized"),
+    ("list.by_priority", "{} - par priorité", "{} - by priority"),
+    ("list.priority_group", "({} restant(s) sur {})", "({} remaining of {})"),
+    ("list.completed_separator", "── terminés ──", "── done ──"),
+    ("detail.title", "Élément {}", "Item {}"),
+    ("detail.title_stats", "Titre: {} caractère(s), {} mot(s)", "Title: {} character(s), {} word(s)"),
+    ("detail.created", "Créé le: {} ({})", "Created: {} ({})"),
+    ("detail.status", "État: {} - Priorité: {}", "Status: {} - Priority: {}"),
+    ("archives.title", "{} - archives", "{} - archives"),
+    ("archives.count", "Éléments archivés: {}", "Archived items: {}"),
+    ("archives.empty", "Aucun élément archivé.", "No archived items."),
+    ("chart.week_of", "semaine du {}", "week of {}"),
+    ("export.last_modified", "*Dernière modification: {}*", "*Last modified: {}*"),
+    ("error.title_not_found", "aucun élément ne porte ce titre", "no item has this title"),
+    ("error.title_ambiguous", "plusieurs éléments portent ce titre (IDs {})", "several items have this title (IDs {})"),
+    ("error.item_not_found", "élément introuvable", "item not found"),
+    ("error.pending_subtasks", "sous-tâches non terminées: {}", "unfinished subtasks: {}"),
+    ("lock.locked", "liste déjà en cours de modification", "list already being edited"),
+    ("lock.by_pid", " par le processus {}", " by process {}"),
+    ("lock.since", " depuis le {}", " since {}"),
+    ("lock.io", "impossible de créer le verrou: {}", "could not create the lock: {}"),
+    ("age.just_now", "à l'instant", "just now"),
+    ("age.ago", "il y a {} {}", "{} {} ago"),
+    ("age.minute", "minute", "minute"),
+    ("age.minutes", "minutes", "minutes"),
+    ("age.hour", "heure", "hour"),
+    ("age.hours", "heures", "hours"),
+    ("age.day", "jour", "day"),
+    ("age.days", "jours", "days"),
+    ("age.month", "mois", "month"),
+    ("age.months", "mois", "months"),
+    ("age.year", "an", "year"),
+    ("age.years", "ans", "years"),
+    ("main.greeting", "Bonjour! Choisissez une action parmi ce que vous souhaitez faire :", "Hello! Choose what you would like to do:"),
+    ("main.create_list", "Créer une nouvelle liste", "Create a new list"),
+    ("main.update_list", "Mettre à jour une liste existante", "Update an existing list"),
+    ("main.delete_list", "Supprimer une liste existante", "Delete an existing list"),
+    ("main.search", "Rechercher une tâche", "Search for a task"),
+    ("main.export_markdown", "Exporter une liste en Markdown", "Export a list to Markdown"),
+    ("main.export_csv", "Exporter une liste en CSV", "Export a list to CSV"),
+    ("main.import_csv", "Importer une liste depuis un CSV", "Import a list from a CSV file"),
+    ("main.duplicate_list", "Dupliquer une liste", "Duplicate a list"),
+    ("main.resume_last", "Reprendre la dernière liste", "Resume the last list"),
+    ("main.backup_all", "Sauvegarder toutes les listes", "Back up all lists"),
+    ("main.restore_backup", "Restaurer une sauvegarde", "Restore a backup"),
+    ("main.move_task", "Déplacer une tâche vers une autre liste", "Move a task to another list"),
+    ("main.overdue", "Tâches en retard", "Overdue tasks"),
+    ("main.duplicates", "Rechercher les listes en double", "Find duplicate lists"),
+    ("main.dashboard", "Tableau de bord", "Dashboard"),
+    ("main.history", "Historique d'une liste", "List history"),
+    ("main.completion_chart", "Graphique des tâches terminées", "Completed tasks chart"),
+    ("main.trash", "Corbeille", "Trash"),
+    ("main.import_text", "Importer une liste depuis un fichier texte", "Import a list from a text file"),
+    ("main.quick_capture", "Capture rapide", "Quick capture"),
+    ("main.global_stats", "Statistiques globales", "Global statistics"),
+    ("main.edit_json", "Éditer le JSON d'une liste", "Edit a list's JSON"),
+    ("main.import_clipboard", "Importer depuis le presse-papiers", "Import from the clipboard"),
+    ("main.oldest_task", "Plus vieille tâche en attente", "Oldest pending task"),
+    ("main.export_ics", "Exporter une liste en iCalendar", "Export a list to iCalendar"),
+    ("main.quit", "Quitter", "Quit"),
+    ("oldest.found", "Votre plus vieille tâche en attente: [{}] {} (liste '{}', créée {})", "Your oldest pending task: [{}] {} (list '{}', created {})"),
+    ("oldest.none", "Aucune tâche en attente, tout est à jour!", "Nothing pending, you're all caught up!"),
+    ("review.title", "Revue du {}", "Review for {}"),
+    ("review.overdue", "En retard ({}):", "Overdue ({}):"),
+    ("review.due_today", "À faire aujourd'hui ({}):", "Due today ({}):"),
+    ("review.completed_today", "Terminé aujourd'hui ({}):", "Completed today ({}):"),
+    ("review.nothing", "rien", "nothing"),
+    ("review.late", "(échéance {}, {} jour(s) de retard)", "(due {}, {} day(s) late)"),
+    ("modify.title", "Actions disponibles:", "Available actions:"),
+    ("modify.add_item", "Ajouter un élément", "Add an item"),
+    ("modify.change_status", "Changer l'état d'un élément", "Change an item's status"),
+    ("modify.toggle", "Marquer un élément comme terminé/non terminé", "Mark an item as done/not done"),
+    ("modify.remove", "Supprimer un élément", "Delete an item"),
+    ("modify.priority", "Modifier la priorité d'un élément", "Change an item's priority"),
+    ("modify.due_date", "Modifier la date d'échéance d'un élément", "Change an item's due date"),
+    ("modify.display", "Afficher la liste", "Show the list"),
+    ("modify.display_sorted", "Afficher la liste triée", "Show the sorted list"),
+    ("modify.edit_item", "Modifier un élément", "Edit an item"),
+    ("modify.filter_tag", "Filtrer par tag", "Filter by tag"),
+    ("modify.archive_completed", "Archiver les éléments terminés", "Archive completed items"),
+    ("modify.show_archives", "Afficher les archives", "Show archives"),
+    ("modify.complete_many", "Terminer plusieurs éléments (ex: 2-5,8)", "Complete several items (e.g. 2-5,8)"),
+    ("modify.add_subtask", "Ajouter une sous-tâche", "Add a subtask"),
+    ("modify.toggle_subtask", "Cocher/décocher une sous-tâche", "Check/uncheck a subtask"),
+    ("modify.move_item", "Déplacer un élément", "Move an item"),
+    ("modify.filter_status", "Afficher les éléments restants ou terminés", "Show pending or completed items"),
+    ("modify.add_note", "Ajouter une note à un élément", "Add a note to an item"),
+    ("modify.clear_completed", "Supprimer tous les éléments terminés", "Delete all completed items"),
+    ("modify.star", "Ajouter/retirer l'étoile d'un élément", "Star/unstar an item"),
+    ("modify.workload", "Afficher la charge de travail restante", "Show the remaining workload"),
+    ("modify.filter_assignee", "Filtrer par responsable", "Filter by assignee"),
+    ("modify.advance", "Faire avancer l'état d'un élément (À faire → En cours → Terminée)", "Advance an item's status (To do → In progress → Done)"),
+    ("modify.item_detail", "Afficher le détail d'un élément", "Show an item's details"),
+    ("modify.bulk_add", "Ajouter en lot (une tâche par ligne)", "Bulk add (one task per line)"),
+    ("modify.metadata", "Définir/effacer une métadonnée (url, lieu...)", "Set/clear metadata (url, place...)"),
+    ("modify.postpone", "Reporter l'échéance d'un élément", "Postpone an item's due date"),
+    ("modify.next_task", "Prochaine tâche", "Next task"),
+    ("modify.renumber", "Renuméroter les éléments (IDs à partir de 1)", "Renumber items (IDs from 1)"),
+    ("modify.completed_between", "Éléments terminés sur une période", "Items completed over a period"),
+    ("modify.category", "Définir la catégorie d'un élément", "Set an item's category"),
+    ("modify.by_category", "Afficher par catégorie", "Show by category"),
+    ("modify.reset_view", "Rétablir l'affichage par défaut", "Restore the default view"),
+    ("modify.links", "Ajouter/retirer un lien", "Add/remove a link"),
+    ("modify.follow_up", "Terminer un élément et ajouter sa suite", "Complete an item and add its follow-up"),
+    ("modify.by_priority", "Afficher par priorité", "Show by priority"),
+    ("modify.postpone_overdue", "Reporter toutes les échéances dépassées", "Postpone all overdue items"),
+    ("modify.back", "Retour au menu principal", "Back to the main menu"),
+];
+
+// une clé absente du catalogue est renvoyée telle quelle, pour qu'un oubli se voie à l'écran
+pub fn tr_in(language: Language, key: &str) -> &str {
+    match MESSAGES.iter().find(|(k, _, _)| *k == key) {
+        Some((_, fr, en)) => match language {
+            Language::Fr => fr,
+            Language::En => en,
+        },
+        None => key,
+    }
+}
+
+// message avec des valeurs, dans la langue donnée
+pub fn tr_args_in(language: Language, key: &str, args: &[&dyn fmt::Display]) -> String {
+    fill(tr_in(language, key), args)
+}
+
+// remplace les "{}" du message, dans l'ordre, par les valeurs données
+pub fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut result = parts.next().unwrap_or_default().to_string();
+    let mut args = args.iter();
+    for part in parts {
+        match args.next() {
+            Some(arg) => result.push_str(&arg.to_string()),
+            None => result.push_str("{}"),
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_keys_are_unique_and_translations_match() {
+        for (i, (key, fr, en)) in MESSAGES.iter().enumerate() {
+            assert!(MESSAGES[..i].iter().all(|(other, _, _)| other != key), "clé en double: {}", key);
+            assert_eq!(fr.matches("{}").count(), en.matches("{}").count(), "valeurs différentes pour {}", key);
+        }
+        assert_eq!(tr_in(Language::En, "main.quit"), "Quit");
+        assert_eq!(tr_in(Language::Fr, "clé.inconnue"), "clé.inconnue");
+        assert_eq!(Language::from_code("en_US.UTF-8"), Some(Language::En));
+        assert_eq!(Language::from_code("C"), None);
+        assert_eq!(fill(tr_in(Language::Fr, "list.shown"), &[&2, &5]), "Éléments affichés: 2/5");
+    }
+}
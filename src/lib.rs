@@ -0,0 +1,3730 @@
+// types et opérations des todo lists (ajout, modification, sauvegarde, recherche),
+// utilisables par un autre programme sans le menu interactif de main.rs
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Datelike, Duration, Utc, NaiveDate, Local};
+
+mod i18n;
+mod pattern;
+pub use i18n::Language;
+pub use pattern::{Pattern, PatternError};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+impl Priority {
+    pub fn to_string(self) -> &'static str {
+        self.name_in(config().language())
+    }
+
+    pub fn name_in(self, language: Language) -> &'static str {
+        match self {
+            Priority::Low => i18n::tr_in(language, "priority.low"),
+            Priority::Medium => i18n::tr_in(language, "priority.medium"),
+            Priority::High => i18n::tr_in(language, "priority.high"),
+            Priority::Critical => i18n::tr_in(language, "priority.critical"),
+        }
+    }
+
+    pub fn to_label(self) -> String {
+        self.label_in(config().language())
+    }
+
+    pub fn label_in(self, language: Language) -> String {
+        format!("[{}]", self.name_in(language).to_uppercase())
+    }
+
+    pub fn to_symbol(self) -> &'static str {
+        match self {
+            Priority::Low => "🟢",
+            Priority::Medium => "🟡",
+            Priority::High => "🟠",
+            Priority::Critical => "🔴",
+        }
+    }
+
+    // couleur de l'en-tête du groupe dans display_by_priority
+    fn color(self) -> &'static str {
+        match self {
+            Priority::Low => CYAN,
+            Priority::Medium => YELLOW,
+            Priority::High => MAGENTA,
+            Priority::Critical => RED,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TaskStatus {
+    Afaire,
+    EnCours,
+    EnAttente,
+    Terminee,
+}
+
+impl TaskStatus {
+    pub fn to_string(self) -> &'static str {
+        self.name_in(config().language())
+    }
+
+    pub fn name_in(self, language: Language) -> &'static str {
+        match self {
+            TaskStatus::Afaire => i18n::tr_in(language, "status.todo"),
+            TaskStatus::EnCours => i18n::tr_in(language, "status.in_progress"),
+            TaskStatus::EnAttente => i18n::tr_in(language, "status.waiting"),
+            TaskStatus::Terminee => i18n::tr_in(language, "status.done"),
+        }
+    }
+
+    pub fn to_symbol(self) -> &'static str {
+        match self {
+            TaskStatus::Afaire => "□",
+            TaskStatus::EnCours => "▶",
+            TaskStatus::EnAttente => "⏸",
+            TaskStatus::Terminee => "✓",
+        }
+    }
+    pub fn is_done(self) -> bool {
+        matches!(self, TaskStatus::Terminee)
+    }
+
+    // À faire -> En cours -> Terminée -> À faire; une tâche en attente reprend
+    pub fn next(self) -> TaskStatus {
+        match self {
+            TaskStatus::Afaire => TaskStatus::EnCours,
+            TaskStatus::EnCours => TaskStatus::Terminee,
+            TaskStatus::EnAttente => TaskStatus::EnCours,
+            TaskStatus::Terminee => TaskStatus::Afaire,
+        }
+    }
+
+    // code couleur ANSI de l'état, distinct de celui du titre
+    fn color(self) -> &'static str {
+        match self {
+            TaskStatus::Afaire => MAGENTA,
+            TaskStatus::EnCours => CYAN,
+            TaskStatus::EnAttente => YELLOW,
+            TaskStatus::Terminee => GREEN,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Recurrence {
+    pub fn to_string(self) -> &'static str {
+        self.name_in(config().language())
+    }
+
+    pub fn name_in(self, language: Language) -> &'static str {
+        match self {
+            Recurrence::Daily => i18n::tr_in(language, "recurrence.daily"),
+            Recurrence::Weekly => i18n::tr_in(language, "recurrence.weekly"),
+            Recurrence::Monthly => i18n::tr_in(language, "recurrence.monthly"),
+        }
+    }
+
+    pub fn advance(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Recurrence::Daily => date + chrono::Days::new(1),
+            Recurrence::Weekly => date + chrono::Days::new(7),
+            // le 31 janvier devient le 28 (ou 29) février
+            Recurrence::Monthly => date + chrono::Months::new(1),
+        }
+    }
+}
+
+// couleur choisie pour la catégorie d'un élément
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum LabelColor {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl LabelColor {
+    pub const ALL: [LabelColor; 6] =
+        [LabelColor::Red, LabelColor::Green, LabelColor::Yellow, LabelColor::Blue, LabelColor::Magenta, LabelColor::Cyan];
+
+    pub fn to_string(self) -> &'static str {
+        self.name_in(config().language())
+    }
+
+    pub fn name_in(self, language: Language) -> &'static str {
+        match self {
+            LabelColor::Red => i18n::tr_in(language, "color.red"),
+            LabelColor::Green => i18n::tr_in(language, "color.green"),
+            LabelColor::Yellow => i18n::tr_in(language, "color.yellow"),
+            LabelColor::Blue => i18n::tr_in(language, "color.blue"),
+            LabelColor::Magenta => i18n::tr_in(language, "color.magenta"),
+            LabelColor::Cyan => i18n::tr_in(language, "color.cyan"),
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            LabelColor::Red => RED,
+            LabelColor::Green => GREEN,
+            LabelColor::Yellow => YELLOW,
+            LabelColor::Blue => BLUE,
+            LabelColor::Magenta => MAGENTA,
+            LabelColor::Cyan => CYAN,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Priority,
+    DueDate,
+    Id,
+    CreatedAt,
+    Status,
+    Title,
+    Manual,
+}
+
+impl SortKey {
+    pub fn to_string(self) -> &'static str {
+        self.name_in(config().language())
+    }
+
+    pub fn name_in(self, language: Language) -> &'static str {
+        match self {
+            SortKey::Priority => i18n::tr_in(language, "sort.priority"),
+            SortKey::DueDate => i18n::tr_in(language, "sort.due_date"),
+            SortKey::Id => i18n::tr_in(language, "sort.id"),
+            SortKey::CreatedAt => i18n::tr_in(language, "sort.created_at"),
+            SortKey::Status => i18n::tr_in(language, "sort.status"),
+            SortKey::Title => i18n::tr_in(language, "sort.title"),
+            SortKey::Manual => i18n::tr_in(language, "sort.manual"),
+        }
+    }
+
+    pub fn compare(self, a: &TodoItem, b: &TodoItem) -> std::cmp::Ordering {
+        match self {
+            // D'abord par priorité (Critical > High > Medium > Low), puis par date d'échéance
+            SortKey::Priority => (b.priority as u8)
+                .cmp(&(a.priority as u8))
+                .then_with(|| compare_due_dates(a, b)),
+            // Échéances les plus proches en premier, éléments sans date à la fin
+            SortKey::DueDate => compare_due_dates(a, b),
+            SortKey::Id => a.id.cmp(&b.id),
+            SortKey::CreatedAt => a.created_at.cmp(&b.created_at),
+            SortKey::Status => a.status.is_done().cmp(&b.status.is_done()).then(a.id.cmp(&b.id)),
+            SortKey::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            SortKey::Manual => a.position.cmp(&b.position),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ItemFilter {
+    All,
+    Completed,
+    Incomplete,
+}
+
+impl ItemFilter {
+    pub fn to_string(self) -> &'static str {
+        self.name_in(config().language())
+    }
+
+    pub fn name_in(self, language: Language) -> &'static str {
+        match self {
+            ItemFilter::All => i18n::tr_in(language, "filter.all"),
+            ItemFilter::Completed => i18n::tr_in(language, "filter.completed"),
+            ItemFilter::Incomplete => i18n::tr_in(language, "filter.incomplete"),
+        }
+    }
+
+    pub fn matches(self, item: &TodoItem) -> bool {
+        match self {
+            ItemFilter::All => true,
+            ItemFilter::Completed => item.status.is_done(),
+            ItemFilter::Incomplete => !item.status.is_done(),
+        }
+    }
+}
+
+// dernier affichage choisi pour une liste, repris par display à la réouverture
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct ViewPrefs {
+    #[serde(default)]
+    pub sort: Option<SortKey>,
+    #[serde(default)]
+    pub filter: Option<ItemFilter>,
+    #[serde(default)]
+    pub grouped: bool,
+}
+
+impl ViewPrefs {
+    pub fn describe(self) -> String {
+        self.describe_in(config().language())
+    }
+
+    pub fn describe_in(self, language: Language) -> String {
+        let mut parts = Vec::new();
+        if let Some(sort) = self.sort {
+            parts.push(i18n::tr_args_in(language, "view.sort", &[&sort.name_in(language)]));
+        }
+        if let Some(filter) = self.filter {
+            parts.push(filter.name_in(language).to_string());
+        }
+        if self.grouped {
+            parts.push(i18n::tr_in(language, "view.grouped").to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Subtask {
+    pub title: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Note {
+    pub text: String,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TodoItem {
+    pub id: u32,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub subtasks: Vec<Subtask>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    // épinglé en tête de liste, indépendamment de la priorité
+    #[serde(default)]
+    pub starred: bool,
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    // effort en points (story points), pour la planification
+    #[serde(default)]
+    pub points: Option<u8>,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    // rang dans l'ordre manuel (à partir de 1), toujours égal à la place dans `items`
+    #[serde(default)]
+    pub position: u32,
+    // informations libres (url, lieu...) affichées en "clé: valeur"
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    // chemins de fichiers ou URLs, sans validation
+    #[serde(default)]
+    pub links: Vec<String>,
+    // ID de l'élément terminé dont cette tâche est la suite
+    #[serde(default)]
+    pub follows: Option<u32>,
+    // regroupement de display_grouped, avec une couleur facultative pour son en-tête
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub category_color: Option<LabelColor>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TodoList {
+    pub name: String,
+    pub items: Vec<TodoItem>,
+    // éléments terminés retirés de l'affichage par défaut mais conservés dans le fichier
+    #[serde(default)]
+    pub archived: Vec<TodoItem>,
+    // compteur monotone: un ID n'est jamais réattribué, même après suppression
+    #[serde(default)]
+    next_id: u32,
+    pub created_at: DateTime<Utc>,
+    pub last_modified: DateTime<Utc>,
+    // vrai dès que l'utilisateur a déplacé un élément: l'ordre manuel devient l'affichage par défaut
+    #[serde(default)]
+    pub manual_order: bool,
+    // None: affichage par défaut (tous les éléments, tri de default_sort)
+    #[serde(default)]
+    pub view_prefs: Option<ViewPrefs>,
+    // version du format du fichier, voir migrate (0 pour les fichiers qui n'en ont pas)
+    #[serde(default)]
+    pub schema_version: u32,
+    // opérations pas encore ajoutées au fichier d'historique (écrites par save_todo_list)
+    #[serde(skip)]
+    history: Vec<HistoryEntry>,
+    // nombre de modifications pas encore écrites sur le disque, remis à zéro par save_todo_list
+    #[serde(skip)]
+    unsaved_changes: u32,
+    // nombre d'éléments renumérotés par compact_ids, en attendant que l'appelant le signale
+    #[serde(skip)]
+    compacted: Option<usize>,
+}
+
+impl TodoItem {
+    pub fn new(id: u32, title: String, description: Option<String>, priority: Priority, due_date: Option<NaiveDate>) -> Self {
+        TodoItem {
+            id,
+            title,
+            description,
+            status: TaskStatus::Afaire,
+            priority,
+            due_date,
+            created_at: Utc::now(),
+            completed_at: None,
+            tags: Vec::new(),
+            subtasks: Vec::new(),
+            recurrence: None,
+            notes: Vec::new(),
+            starred: false,
+            estimate_minutes: None,
+            points: None,
+            assignee: None,
+            position: 0,
+            metadata: BTreeMap::new(),
+            links: Vec::new(),
+            follows: None,
+            category: None,
+            category_color: None,
+        }
+    }
+
+    pub fn mark_completed(&mut self) {
+        self.status = TaskStatus::Terminee;
+        self.completed_at = Some(Utc::now());
+    }
+
+    pub fn mark_incomplete(&mut self) {
+        self.status = TaskStatus::Afaire;
+        self.completed_at = None;
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim().trim_start_matches('#');
+        self.tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag))
+    }
+
+    pub fn is_assigned_to(&self, who: &str) -> bool {
+        let who = who.trim().trim_start_matches('@');
+        self.assignee.as_ref().is_some_and(|assignee| assignee.to_lowercase() == who.to_lowercase())
+    }
+
+    pub fn is_overdue(&self) -> bool {
+        if let Some(due_date) = self.due_date {
+            if self.status.is_done() {
+                return false;
+            }
+            let today = Local::now().date_naive();
+            due_date < today
+        } else {
+            false
+        }
+    }
+
+    pub fn display(&self) {
+        print!("{}", self.render(&DisplayOptions::from_config()));
+    }
+
+    // bloc tel qu'affiché dans une liste, le titre tronqué à options.title_width
+    pub fn render(&self, options: &DisplayOptions) -> String {
+        let mut block = String::new();
+        let _ = self.write_block(&mut block, options, true);
+        block
+    }
+
+    // bloc complet, sans tronquer le titre (vue détaillée)
+    pub fn detail_block(&self, options: &DisplayOptions) -> String {
+        let mut block = String::new();
+        let _ = self.write_block(&mut block, options, false);
+        block
+    }
+
+    fn write_block(&self, out: &mut dyn fmt::Write, options: &DisplayOptions, truncate_title: bool) -> fmt::Result {
+        let status_symbol = self.status.to_symbol();
+        let status_text = self.status.name_in(options.language);
+        let priority_symbol = self.priority.to_symbol();
+        let priority_label = self.priority.label_in(options.language);
+        let tags: String = self.tags.iter().map(|tag| format!(" #{}", tag)).collect();
+        let assignee = self.assignee.as_ref().map(|who| format!(" (@{})", who)).unwrap_or_default();
+        let progress = if self.subtasks.is_empty() {
+            String::new()
+        } else {
+            let done = self.subtasks.iter().filter(|subtask| subtask.done).count();
+            format!(" ({}/{})", done, self.subtasks.len())
+        };
+        let points = self.points.map(|points| format!(" ({}pts)", points)).unwrap_or_default();
+        let title = if truncate_title { truncate(&self.title, options.title_width) } else { self.title.clone() };
+        let title = if self.status.is_done() {
+            options.paint(&title, GREEN)
+        } else if self.is_overdue() {
+            options.paint(&title, RED)
+        } else {
+            title
+        };
+        let status_color = self.status.color();
+        let star = if self.starred { "⭐ " } else { "" };
+        writeln!(
+            out,
+            "{}{} [{}] {} {}{}{}{}{} - {} {}",
+            star,
+            options.paint(status_symbol, status_color),
+            self.id,
+            priority_label,
+            title,
+            points,
+            progress,
+            tags,
+            assignee,
+            options.paint(status_text, status_color),
+            priority_symbol
+        )?;
+
+        if let Some(previous) = self.follows {
+            writeln!(out, "    {}", options.tr_args("item.follows", &[&previous]))?;
+        }
+        
+        if let Some(desc) = &self.description {
+            // les lignes suivantes sont alignées sous la première, coupées à la largeur du terminal
+            let prefix = format!("    {}: ", options.tr("item.description"));
+            let width = options.width.saturating_sub(prefix.len()).max(20);
+            let lines = desc.lines().flat_map(|line| wrap_text(line, width));
+            for (i, line) in lines.enumerate() {
+                if i == 0 {
+                    writeln!(out, "{}{}", prefix, line)?;
+                } else {
+                    writeln!(out, "{:width$}{}", "", line, width = prefix.len())?;
+                }
+            }
+        }
+
+        for (key, value) in &self.metadata {
+            writeln!(out, "    {}: {}", key, value)?;
+        }
+
+        for link in &self.links {
+            writeln!(out, "    → {}", options.paint(link, CYAN))?;
+        }
+
+        for (i, subtask) in self.subtasks.iter().enumerate() {
+            let checkbox = if subtask.done { "☑" } else { "☐" };
+            writeln!(out, "    {}. {} {}", i + 1, checkbox, subtask.title)?;
+        }
+
+        for note in &self.notes {
+            writeln!(out, "    📝 {} : {}", options.format_datetime(note.added_at), note.text)?;
+        }
+        
+        if let Some(due_date) = self.due_date {
+            let date_str = options.format_date(due_date);
+            if self.is_overdue() {
+                writeln!(out, "    {}", options.paint(&options.tr_args("item.overdue", &[&date_str]), RED))?;
+            } else if let Some(days) = self.days_until_due() {
+                let line = match days {
+                    0 => options.tr_args("item.due_today", &[&date_str]),
+                    1 => options.tr_args("item.due_tomorrow", &[&date_str]),
+                    ..7 => options.tr_args("item.due_in_days", &[&date_str, &days]),
+                    _ => options.tr_args("item.due", &[&date_str]),
+                };
+                writeln!(out, "    {}", line)?;
+            }
+        }
+        
+        if let Some(recurrence) = self.recurrence {
+            writeln!(out, "    {}", options.tr_args("item.recurrence", &[&recurrence.name_in(options.language)]))?;
+        }
+        
+        if self.status.is_done()
+            && options.show_completed_at
+            && let Some(completed_at) = self.completed_at
+        {
+            writeln!(out, "    {}", options.tr_args("item.completed_at", &[&options.format_datetime(completed_at), &humanize_age_in(completed_at, options.language)]))?;
+        }
+
+        match (self.estimate_minutes, self.actual_minutes()) {
+            (Some(estimate), Some(actual)) => {
+                let line = options.tr_args("item.estimate_actual", &[&format_minutes(estimate as i64), &format_minutes(actual)]);
+                writeln!(out, "    {}", line)?;
+            }
+            (Some(estimate), None) => writeln!(out, "    {}", options.tr_args("item.estimate", &[&format_minutes(estimate as i64)]))?,
+            (None, Some(actual)) => writeln!(out, "    {}", options.tr_args("item.actual", &[&format_minutes(actual)]))?,
+            (None, None) => {}
+        }
+        writeln!(out)
+    }
+
+    // temps écoulé entre la création et la complétion
+    pub fn actual_minutes(&self) -> Option<i64> {
+        if !self.status.is_done() {
+            return None;
+        }
+        self.completed_at.map(|completed_at| (completed_at - self.created_at).num_minutes().max(0))
+    }
+
+    pub fn days_until_due(&self) -> Option<i64> {
+        if let Some(due_date) = self.due_date {
+            let today = Local::now().date_naive();
+            Some((due_date - today).num_days())
+        } else {
+            None
+        }
+    }
+}
+
+// avec les options par défaut (voir DisplayOptions); l'écran passe par display ou render
+impl fmt::Display for TodoItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_block(f, &DisplayOptions::default(), true)
+    }
+}
+
+// échec de la recherche d'un élément par son titre
+#[derive(Debug, Clone, PartialEq)]
+pub enum LookupError {
+    NotFound,
+    // IDs de tous les éléments qui portent ce titre
+    Ambiguous(Vec<u32>),
+}
+
+impl std::fmt::Display for LookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LookupError::NotFound => write!(f, "{}", tr("error.title_not_found")),
+            LookupError::Ambiguous(ids) => {
+                let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+                write!(f, "{}", tr_args("error.title_ambiguous", &[&ids.join(", ")]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LookupError {}
+
+// échec d'une action qui termine ou rouvre un élément, désigné par son ID ou par son titre
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToggleError {
+    NotFound,
+    // IDs de tous les éléments qui portent le titre demandé
+    Ambiguous(Vec<u32>),
+    // titres des sous-tâches à cocher avant de pouvoir terminer l'élément (voir SubtaskRule)
+    PendingSubtasks(Vec<String>),
+}
+
+impl std::fmt::Display for ToggleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ToggleError::NotFound => write!(f, "{}", tr("error.item_not_found")),
+            ToggleError::Ambiguous(ids) => LookupError::Ambiguous(ids.clone()).fmt(f),
+            ToggleError::PendingSubtasks(titles) => write!(f, "{}", tr_args("error.pending_subtasks", &[&titles.join(", ")])),
+        }
+    }
+}
+
+impl std::error::Error for ToggleError {}
+
+impl From<LookupError> for ToggleError {
+    fn from(error: LookupError) -> Self {
+        match error {
+            LookupError::NotFound => ToggleError::NotFound,
+            LookupError::Ambiguous(ids) => ToggleError::Ambiguous(ids),
+        }
+    }
+}
+
+// ce que font les méthodes qui terminent un élément quand il lui reste des sous-tâches à cocher:
+// Enforce refuse (ToggleError::PendingSubtasks), Ignore termine quand même; voir Config::subtask_rule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubtaskRule {
+    Enforce,
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HistoryAction {
+    Add,
+    Complete,
+    Reopen,
+    Edit,
+    Archive,
+    Remove,
+}
+
+impl HistoryAction {
+    pub fn to_string(&self) -> &'static str {
+        self.name_in(config().language())
+    }
+
+    pub fn name_in(&self, language: Language) -> &'static str {
+        match self {
+            HistoryAction::Add => i18n::tr_in(language, "history.add"),
+            HistoryAction::Complete => i18n::tr_in(language, "history.complete"),
+            HistoryAction::Reopen => i18n::tr_in(language, "history.reopen"),
+            HistoryAction::Edit => i18n::tr_in(language, "history.edit"),
+            HistoryAction::Archive => i18n::tr_in(language, "history.archive"),
+            HistoryAction::Remove => i18n::tr_in(language, "history.remove"),
+        }
+    }
+
+    // changement d'état: terminé, rouvert ou simple modification
+    fn for_status(was_done: bool, is_done: bool) -> Self {
+        match (was_done, is_done) {
+            (false, true) => HistoryAction::Complete,
+            (true, false) => HistoryAction::Reopen,
+            _ => HistoryAction::Edit,
+        }
+    }
+}
+
+// une ligne du fichier d'historique d'une liste
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub action: HistoryAction,
+    pub item_id: u32,
+    pub title: String,
+}
+
+impl TodoList {
+    pub fn new(name: String) -> Self {
+        TodoList {
+            name,
+            items: Vec::new(),
+            archived: Vec::new(),
+            next_id: 1,
+            created_at: Utc::now(),
+            last_modified: Utc::now(),
+            manual_order: false,
+            view_prefs: None,
+            schema_version: SCHEMA_VERSION,
+            history: Vec::new(),
+            unsaved_changes: 0,
+            compacted: None,
+        }
+    }
+
+    // à appeler après chaque modification
+    fn touch(&mut self) {
+        self.renumber_positions();
+        self.last_modified = Utc::now();
+        self.mark_changed();
+    }
+
+    fn mark_changed(&mut self) {
+        self.unsaved_changes = self.unsaved_changes.saturating_add(1);
+    }
+
+    // le titre est relevé tout de suite: après une suppression, l'élément n'existe plus
+    fn record(&mut self, action: HistoryAction, id: u32) {
+        let title = self
+            .items
+            .iter()
+            .chain(&self.archived)
+            .find(|item| item.id == id)
+            .map(|item| item.title.clone())
+            .unwrap_or_default();
+        self.history.push(HistoryEntry { timestamp: Utc::now(), action, item_id: id, title });
+    }
+
+    fn renumber_positions(&mut self) {
+        for (i, item) in self.items.iter_mut().enumerate() {
+            item.position = u32::try_from(i + 1).unwrap_or(u32::MAX);
+        }
+    }
+
+    // les anciens fichiers n'ont pas de positions: on garde l'ordre du fichier
+    fn backfill_positions(&mut self) {
+        if self.items.iter().all(|item| item.position > 0) {
+            self.items.sort_by_key(|item| item.position);
+        }
+        self.renumber_positions();
+    }
+
+    // tri utilisé par display: celui de la vue enregistrée, sinon l'ordre manuel s'il a été choisi, sinon la priorité
+    pub fn default_sort(&self) -> SortKey {
+        match self.view_prefs.and_then(|prefs| prefs.sort) {
+            Some(key) => key,
+            None if self.manual_order => SortKey::Manual,
+            None => SortKey::Priority,
+        }
+    }
+
+    // un changement d'affichage n'est pas une modification du contenu: last_modified ne bouge pas
+    pub fn update_view(&mut self, change: impl FnOnce(&mut ViewPrefs)) {
+        let mut prefs = self.view_prefs.unwrap_or_default();
+        change(&mut prefs);
+        self.view_prefs = (prefs != ViewPrefs::default()).then_some(prefs);
+        self.mark_changed();
+    }
+
+    pub fn reset_view(&mut self) {
+        if self.view_prefs.take().is_some() {
+            self.mark_changed();
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.unsaved_changes > 0
+    }
+
+    pub fn unsaved_changes(&self) -> u32 {
+        self.unsaved_changes
+    }
+
+    // Some(nombre d'éléments) si les IDs ont été renumérotés depuis le dernier appel
+    pub fn take_compaction(&mut self) -> Option<usize> {
+        self.compacted.take()
+    }
+
+    // u32::MAX n'est jamais attribué: il signale que le compteur est épuisé
+    fn allocate_id(&mut self) -> u32 {
+        if self.next_id == u32::MAX {
+            self.compact_ids();
+        }
+        let id = self.next_id;
+        // arrivé à u32::MAX, le compteur est épuisé: compact_ids au prochain appel
+        self.next_id = id.saturating_add(1);
+        id
+    }
+
+    // renumérote éléments et archives à partir de 1 plutôt que de réutiliser un ID
+    // l'appelant l'apprend par take_compaction
+    fn compact_ids(&mut self) {
+        self.renumber();
+        self.compacted = Some(self.items.len() + self.archived.len());
+    }
+
+    // IDs consécutifs à partir de 1 dans l'ordre d'affichage, puis les archives; l'historique
+    // et les notes qui citent un ancien ID ne sont pas mis à jour
+    pub fn renumber(&mut self) {
+        let key = self.default_sort();
+        let mut order: Vec<usize> = (0..self.items.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.items[a], &self.items[b]);
+            b.starred.cmp(&a.starred).then_with(|| key.compare(a, b))
+        });
+        let mut next_id: u32 = 1;
+        let mut new_ids = BTreeMap::new();
+        for index in order {
+            new_ids.insert(self.items[index].id, next_id);
+            self.items[index].id = next_id;
+            next_id += 1;
+        }
+        for item in &mut self.archived {
+            new_ids.insert(item.id, next_id);
+            item.id = next_id;
+            next_id += 1;
+        }
+        // les liens "suite de" suivent la renumérotation; un élément supprimé depuis n'a plus d'ID
+        for item in self.items.iter_mut().chain(&mut self.archived) {
+            item.follows = item.follows.and_then(|previous| new_ids.get(&previous).copied());
+        }
+        self.next_id = next_id;
+        self.touch();
+    }
+
+    // les anciens fichiers n'ont pas de compteur: on repart du plus grand ID existant
+    fn backfill_next_id(&mut self) {
+        let max_id = self.items.iter().chain(&self.archived).map(|item| item.id).max().unwrap_or(0);
+        if self.next_id <= max_id {
+            self.next_id = max_id.saturating_add(1);
+        }
+    }
+
+    pub fn add_item(&mut self, title: String, description: Option<String>) {
+        let id = self.allocate_id();
+        let item = TodoItem::new(id, title, description, Priority::default(), None);
+        self.items.push(item);
+        self.record(HistoryAction::Add, id);
+        self.touch();
+    }
+
+    // termine l'élément (s'il ne l'est pas déjà) et ajoute la tâche qui lui fait suite;
+    // renvoie l'ID de la nouvelle tâche
+    pub fn complete_with_follow_up(
+        &mut self,
+        id: u32,
+        title: String,
+        description: Option<String>,
+        rule: SubtaskRule,
+    ) -> Result<u32, ToggleError> {
+        let item = self.items.iter().find(|item| item.id == id).ok_or(ToggleError::NotFound)?;
+        if !item.status.is_done() {
+            self.toggle_item(id, rule)?;
+        }
+        let follow_up_id = self.allocate_id();
+        let mut follow_up = TodoItem::new(follow_up_id, title, description, Priority::default(), None);
+        follow_up.follows = Some(id);
+        self.items.push(follow_up);
+        self.record(HistoryAction::Add, follow_up_id);
+        self.touch();
+        Ok(follow_up_id)
+    }
+
+    // un élément par ligne non vide (titre seul), renvoie le nombre d'éléments ajoutés
+    pub fn add_items_bulk<'a>(&mut self, lines: impl IntoIterator<Item = &'a str>) -> usize {
+        let mut count = 0;
+        for title in lines.into_iter().map(str::trim).filter(|line| !line.is_empty()) {
+            let id = self.allocate_id();
+            self.items.push(TodoItem::new(id, title.to_string(), None, Priority::default(), None));
+            self.record(HistoryAction::Add, id);
+            count += 1;
+        }
+        if count > 0 {
+            self.touch();
+        }
+        count
+    }
+
+    pub fn add_item_with_details(&mut self, title: String, description: Option<String>, priority: Priority, due_date: Option<NaiveDate>) {
+        let id = self.allocate_id();
+        let item = TodoItem::new(id, title, description, priority, due_date);
+        self.items.push(item);
+        self.record(HistoryAction::Add, id);
+        self.touch();
+    }
+
+    // renvoie l'élément ajouté pour que l'appelant puisse compléter les champs optionnels
+    pub fn add_item_with_details_status(&mut self, title: String, description: Option<String>, status: TaskStatus, priority: Priority, due_date: Option<NaiveDate>) -> &mut TodoItem {
+        let id = self.allocate_id();
+        let mut item = TodoItem::new(id, title, description, priority, due_date);
+        item.status = status;
+        if status.is_done() {
+            item.completed_at = Some(Utc::now());
+        }
+        self.items.push(item);
+        self.record(HistoryAction::Add, id);
+        self.touch();
+        self.items.last_mut().expect("l'élément vient d'être ajouté")
+    }
+
+    // seuls les éléments non terminés comptent: on peut refaire une tâche déjà accomplie
+    pub fn find_by_title(&self, title: &str) -> Option<&TodoItem> {
+        let title = title.trim();
+        self.items
+            .iter()
+            .find(|item| !item.status.is_done() && item.title.trim().to_lowercase() == title.to_lowercase())
+    }
+
+    // pour rappeler à l'utilisateur sur quel élément il va agir
+    pub fn item_title(&self, id: u32) -> Option<&str> {
+        self.items.iter().find(|item| item.id == id).map(|item| item.title.as_str())
+    }
+
+    // ID de l'élément portant ce titre (sans tenir compte de la casse), terminé ou non
+    pub fn resolve_title(&self, title: &str) -> Result<u32, LookupError> {
+        let title = title.trim().to_lowercase();
+        let ids: Vec<u32> = self
+            .items
+            .iter()
+            .filter(|item| item.title.trim().to_lowercase() == title)
+            .map(|item| item.id)
+            .collect();
+        match ids.as_slice() {
+            [] => Err(LookupError::NotFound),
+            [id] => Ok(*id),
+            _ => Err(LookupError::Ambiguous(ids)),
+        }
+    }
+
+    pub fn toggle_by_title(&mut self, title: &str, rule: SubtaskRule) -> Result<(), ToggleError> {
+        let id = self.resolve_title(title)?;
+        self.toggle_item(id, rule)
+    }
+
+    pub fn remove_item(&mut self, id: u32) -> bool {
+        if let Some(index) = self.items.iter().position(|item| item.id == id) {
+            self.record(HistoryAction::Remove, id);
+            self.items.remove(index);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    // déplace un élément à une position (à partir de 1), ramenée aux extrémités si hors limites
+    pub fn move_item(&mut self, id: u32, new_position: usize) -> bool {
+        let Some(index) = self.items.iter().position(|item| item.id == id) else {
+            return false;
+        };
+        let item = self.items.remove(index);
+        let target = new_position.clamp(1, self.items.len() + 1) - 1;
+        self.items.insert(target, item);
+        self.manual_order = true;
+        // un tri enregistré masquerait le déplacement
+        if self.view_prefs.is_some_and(|prefs| prefs.sort.is_some()) {
+            self.update_view(|prefs| prefs.sort = None);
+        }
+        self.touch();
+        true
+    }
+
+    // rouvrir un élément terminé n'est jamais refusé
+    pub fn toggle_item(&mut self, id: u32, rule: SubtaskRule) -> Result<(), ToggleError> {
+        self.check_completion(id, rule)?;
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return Err(ToggleError::NotFound);
+        };
+        if item.status.is_done() {
+            item.mark_incomplete();
+            self.record(HistoryAction::Reopen, id);
+        } else {
+            item.mark_completed();
+            self.record(HistoryAction::Complete, id);
+            self.schedule_next_occurrence(id);
+        }
+        self.touch();
+        Ok(())
+    }
+
+    // vérifie qu'un élément non terminé peut l'être: avec Enforce, toutes ses sous-tâches doivent être cochées
+    fn check_completion(&self, id: u32, rule: SubtaskRule) -> Result<(), ToggleError> {
+        let item = self.items.iter().find(|item| item.id == id).ok_or(ToggleError::NotFound)?;
+        let pending: Vec<String> =
+            item.subtasks.iter().filter(|subtask| !subtask.done).map(|subtask| subtask.title.clone()).collect();
+        if item.status.is_done() || pending.is_empty() || rule == SubtaskRule::Ignore {
+            return Ok(());
+        }
+        Err(ToggleError::PendingSubtasks(pending))
+    }
+
+    pub fn update_item_status(&mut self, id: u32, status: TaskStatus, rule: SubtaskRule) -> Result<(), ToggleError> {
+        if status.is_done() {
+            self.check_completion(id, rule)?;
+        }
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return Err(ToggleError::NotFound);
+        };
+        let was_done = item.status.is_done();
+        item.status = status;
+        if !status.is_done() {
+            item.completed_at = None;
+        } else if !was_done {
+            item.completed_at = Some(Utc::now());
+        }
+        self.record(HistoryAction::for_status(was_done, status.is_done()), id);
+        if status.is_done() && !was_done {
+            self.schedule_next_occurrence(id);
+        }
+        self.touch();
+        Ok(())
+    }
+
+    // une tâche récurrente qui vient d'être terminée est recréée avec l'échéance suivante
+    fn schedule_next_occurrence(&mut self, id: u32) {
+        let Some(item) = self.items.iter().find(|item| item.id == id) else {
+            return;
+        };
+        let Some(recurrence) = item.recurrence else {
+            return;
+        };
+
+        let mut next = item.clone();
+        next.id = self.allocate_id();
+        next.mark_incomplete();
+        next.created_at = Utc::now();
+        let base_date = next.due_date.unwrap_or_else(|| Local::now().date_naive());
+        next.due_date = Some(recurrence.advance(base_date));
+        for subtask in &mut next.subtasks {
+            subtask.done = false;
+        }
+        next.notes.clear();
+        let next_id = next.id;
+        self.items.push(next);
+        self.record(HistoryAction::Add, next_id);
+    }
+
+    // marque comme terminés les éléments listés; renvoie le nombre d'éléments trouvés et terminés,
+    // et les IDs de ceux que la règle des sous-tâches a laissés de côté
+    pub fn toggle_range(&mut self, ids: &[u32], rule: SubtaskRule) -> (usize, Vec<u32>) {
+        let (ids, blocked): (Vec<u32>, Vec<u32>) =
+            ids.iter().partition(|id| !matches!(self.check_completion(**id, rule), Err(ToggleError::PendingSubtasks(_))));
+        let mut completed = Vec::new();
+        let mut count = 0;
+        for item in self.items.iter_mut().filter(|item| ids.contains(&item.id)) {
+            if !item.status.is_done() {
+                item.mark_completed();
+                completed.push(item.id);
+            }
+            count += 1;
+        }
+        for id in completed {
+            self.record(HistoryAction::Complete, id);
+            self.schedule_next_occurrence(id);
+        }
+        if count > 0 {
+            self.touch();
+        }
+        (count, blocked)
+    }
+
+    pub fn add_subtask(&mut self, id: u32, title: String) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.subtasks.push(Subtask { title, done: false });
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn toggle_star(&mut self, id: u32) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.starred = !item.starred;
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn add_note(&mut self, id: u32, text: String) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.notes.push(Note { text, added_at: Utc::now() });
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    // number est la position affichée (à partir de 1); le parent se termine avec sa dernière sous-tâche
+    pub fn toggle_subtask(&mut self, id: u32, number: usize) -> bool {
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return false;
+        };
+        let Some(subtask) = number.checked_sub(1).and_then(|index| item.subtasks.get_mut(index)) else {
+            return false;
+        };
+        subtask.done = !subtask.done;
+        if item.subtasks.iter().all(|subtask| subtask.done) && !item.status.is_done() {
+            item.mark_completed();
+            self.record(HistoryAction::Complete, id);
+            self.schedule_next_occurrence(id);
+        } else {
+            self.record(HistoryAction::Edit, id);
+        }
+        self.touch();
+        true
+    }
+
+    // fait avancer l'état d'un cran, renvoie le nouvel état
+    pub fn cycle_status(&mut self, id: u32, rule: SubtaskRule) -> Result<TaskStatus, ToggleError> {
+        let status = self.items.iter().find(|item| item.id == id).ok_or(ToggleError::NotFound)?.status.next();
+        self.update_item_status(id, status, rule)?;
+        Ok(status)
+    }
+
+    // None retire l'élément de sa catégorie (et sa couleur)
+    pub fn set_category(&mut self, id: u32, category: Option<String>, color: Option<LabelColor>) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.category_color = category.as_ref().and(color);
+            item.category = category;
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    // le lien est seulement débarrassé des espaces autour; un lien vide est refusé
+    pub fn add_link(&mut self, id: u32, link: &str) -> bool {
+        let link = link.trim();
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return false;
+        };
+        if link.is_empty() {
+            return false;
+        }
+        item.links.push(link.to_string());
+        self.record(HistoryAction::Edit, id);
+        self.touch();
+        true
+    }
+
+    // number commence à 1, dans l'ordre d'affichage des liens
+    pub fn remove_link(&mut self, id: u32, number: usize) -> bool {
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return false;
+        };
+        let Some(index) = number.checked_sub(1).filter(|&index| index < item.links.len()) else {
+            return false;
+        };
+        item.links.remove(index);
+        self.record(HistoryAction::Edit, id);
+        self.touch();
+        true
+    }
+
+    // une valeur None efface la clé
+    pub fn set_metadata(&mut self, id: u32, key: String, value: Option<String>) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            match value {
+                Some(value) => item.metadata.insert(key, value),
+                None => item.metadata.remove(&key),
+            };
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn update_item_priority(&mut self, id: u32, priority: Priority) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.priority = priority;
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn update_item_due_date(&mut self, id: u32, due_date: Option<NaiveDate>) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            item.due_date = due_date;
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    // repousse l'échéance; false si l'élément n'existe pas ou n'a pas d'échéance
+    pub fn snooze(&mut self, id: u32, by: Duration) -> bool {
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return false;
+        };
+        let Some(due_date) = item.due_date.and_then(|due_date| due_date.checked_add_signed(by)) else {
+            return false;
+        };
+        item.due_date = Some(due_date);
+        self.record(HistoryAction::Edit, id);
+        self.touch();
+        true
+    }
+
+    // repousse de `by` l'échéance de chaque élément en retard (voir is_overdue), renvoie leur nombre;
+    // les éléments sans échéance ou terminés ne bougent pas
+    pub fn postpone_overdue(&mut self, by: Duration) -> usize {
+        let mut postponed = Vec::new();
+        for item in self.items.iter_mut().filter(|item| item.is_overdue()) {
+            if let Some(due_date) = item.due_date.and_then(|due_date| due_date.checked_add_signed(by)) {
+                item.due_date = Some(due_date);
+                postponed.push(item.id);
+            }
+        }
+        for &id in &postponed {
+            self.record(HistoryAction::Edit, id);
+        }
+        if !postponed.is_empty() {
+            self.touch();
+        }
+        postponed.len()
+    }
+
+    // None laisse le champ inchangé; pour la description, Some(None) l'efface
+    pub fn edit_item(&mut self, id: u32, title: Option<String>, description: Option<Option<String>>) -> bool {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
+            if let Some(title) = title {
+                item.title = title;
+            }
+            if let Some(description) = description {
+                item.description = description;
+            }
+            self.record(HistoryAction::Edit, id);
+            self.touch();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn archive_completed(&mut self) -> usize {
+        let (completed, remaining): (Vec<TodoItem>, Vec<TodoItem>) =
+            self.items.drain(..).partition(|item| item.status.is_done());
+        self.items = remaining;
+        let count = completed.len();
+        if count > 0 {
+            let ids: Vec<u32> = completed.iter().map(|item| item.id).collect();
+            self.archived.extend(completed);
+            for id in ids {
+                self.record(HistoryAction::Archive, id);
+            }
+            self.touch();
+        }
+        count
+    }
+
+    // contrairement à archive_completed, les éléments terminés sont définitivement supprimés
+    pub fn clear_completed(&mut self) -> usize {
+        let before = self.items.len();
+        let ids: Vec<u32> = self.items.iter().filter(|item| item.status.is_done()).map(|item| item.id).collect();
+        for id in ids {
+            self.record(HistoryAction::Remove, id);
+        }
+        self.items.retain(|item| !item.status.is_done());
+        let count = before - self.items.len();
+        if count > 0 {
+            self.touch();
+        }
+        count
+    }
+
+    // éléments terminés entre start et end (bornes comprises), archives comprises, les plus anciens en premier
+    pub fn completed_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&TodoItem> {
+        let mut items: Vec<&TodoItem> = self
+            .items
+            .iter()
+            .chain(&self.archived)
+            .filter(|item| item.status.is_done())
+            .filter(|item| item.completed_at.is_some_and(|completed_at| completed_at >= start && completed_at <= end))
+            .collect();
+        items.sort_by_key(|item| item.completed_at);
+        items
+    }
+
+    // (terminés, total, pourcentage)
+    pub fn stats(&self) -> (usize, usize, f64) {
+        let total = self.items.len();
+        let completed = self.items.iter().filter(|item| item.status.is_done()).count();
+        let percentage = if total == 0 { 0.0 } else { completed as f64 * 100.0 / total as f64 };
+        (completed, total, percentage)
+    }
+
+    // (somme des estimations des éléments restants, nombre d'éléments restants sans estimation)
+    pub fn remaining_estimate(&self) -> (u32, usize) {
+        let remaining = self.items.iter().filter(|item| !item.status.is_done());
+        let (estimated, unestimated): (Vec<&TodoItem>, Vec<&TodoItem>) =
+            remaining.partition(|item| item.estimate_minutes.is_some());
+        let total = estimated.iter().filter_map(|item| item.estimate_minutes).fold(0u32, u32::saturating_add);
+        (total, unestimated.len())
+    }
+
+    // l'élément non terminé créé en premier, celui qui attend depuis le plus longtemps
+    pub fn oldest_incomplete(&self) -> Option<&TodoItem> {
+        self.items.iter().filter(|item| !item.status.is_done()).min_by_key(|item| item.created_at)
+    }
+
+    // somme des points des éléments non terminés (ceux sans points comptent pour 0)
+    pub fn remaining_points(&self) -> u32 {
+        self.items
+            .iter()
+            .filter(|item| !item.status.is_done())
+            .filter_map(|item| item.points)
+            .map(u32::from)
+            .sum()
+    }
+
+    // vue détaillée d'un élément, avec le titre complet
+    pub fn display_item_detail(&self, id: u32) -> bool {
+        let Some(item) = self.items.iter().find(|item| item.id == id) else {
+            return false;
+        };
+        let options = DisplayOptions::from_config();
+        println!("\n{}", options.section_header(&options.tr_args("detail.title", &[&item.id])));
+        let words = item.title.split_whitespace().count();
+        println!("{}", options.tr_args("detail.title_stats", &[&item.title.chars().count(), &words]));
+        let age = humanize_age_in(item.created_at, options.language);
+        println!("{}", options.tr_args("detail.created", &[&options.format_datetime(item.created_at), &age]));
+        let status = item.status.name_in(options.language);
+        println!("{}", options.tr_args("detail.status", &[&status, &item.priority.name_in(options.language)]));
+        println!();
+        print!("{}", item.detail_block(&options));
+        true
+    }
+
+    pub fn display(&self) {
+        print!("{}", self.render(&DisplayOptions::from_config()));
+    }
+
+    // la vue enregistrée de la liste (voir ViewPrefs), telle qu'affichée par display
+    pub fn render(&self, options: &DisplayOptions) -> String {
+        let mut out = String::new();
+        let _ = self.write_view(&mut out, options);
+        out
+    }
+
+    fn write_view(&self, out: &mut dyn fmt::Write, options: &DisplayOptions) -> fmt::Result {
+        let prefs = self.view_prefs.unwrap_or_default();
+        if prefs.grouped {
+            self.write_grouped(out, options)
+        } else {
+            self.write_items(out, &self.filtered_items(prefs.filter.unwrap_or(ItemFilter::All)), options)
+        }
+    }
+
+    pub fn display_sorted(&self, key: SortKey) {
+        let mut sorted_items = self.items.clone();
+        sorted_items.sort_by(|a, b| key.compare(a, b));
+        self.display_items(&sorted_items);
+    }
+
+    pub fn display_filtered(&self, tag: &str) {
+        let filtered_items: Vec<TodoItem> = self.items.iter().filter(|item| item.has_tag(tag)).cloned().collect();
+        self.display_items(&filtered_items);
+    }
+
+    pub fn display_by_assignee(&self, who: &str) {
+        let filtered_items: Vec<TodoItem> = self.items.iter().filter(|item| item.is_assigned_to(who)).cloned().collect();
+        self.display_items(&filtered_items);
+    }
+
+    pub fn display_with(&self, filter: ItemFilter) {
+        self.display_items(&self.filtered_items(filter));
+    }
+
+    fn filtered_items(&self, filter: ItemFilter) -> Vec<TodoItem> {
+        let mut filtered_items: Vec<TodoItem> = self.items.iter().filter(|item| filter.matches(item)).cloned().collect();
+        filtered_items.sort_by(|a, b| self.default_sort().compare(a, b));
+        filtered_items
+    }
+
+    // catégories triées sans tenir compte de la casse ("Maison" et "maison" vont ensemble),
+    // le groupe None (sans catégorie) toujours en dernier
+    pub fn items_by_category(&self) -> Vec<(Option<String>, Vec<&TodoItem>)> {
+        let filter = self.view_prefs.and_then(|prefs| prefs.filter).unwrap_or(ItemFilter::All);
+        let mut sorted_items: Vec<&TodoItem> = self.items.iter().filter(|item| filter.matches(item)).collect();
+        let key = self.default_sort();
+        sorted_items.sort_by(|a, b| b.starred.cmp(&a.starred).then_with(|| key.compare(a, b)));
+
+        let mut groups: BTreeMap<String, (String, Vec<&TodoItem>)> = BTreeMap::new();
+        let mut uncategorized = Vec::new();
+        for item in sorted_items {
+            match &item.category {
+                Some(category) => groups
+                    .entry(category.to_lowercase())
+                    .or_insert_with(|| (category.clone(), Vec::new()))
+                    .1
+                    .push(item),
+                None => uncategorized.push(item),
+            }
+        }
+
+        let mut result: Vec<(Option<String>, Vec<&TodoItem>)> =
+            groups.into_values().map(|(name, items)| (Some(name), items)).collect();
+        if !uncategorized.is_empty() {
+            result.push((None, uncategorized));
+        }
+        result
+    }
+
+    pub fn display_grouped(&self) {
+        let mut out = String::new();
+        let _ = self.write_grouped(&mut out, &DisplayOptions::from_config());
+        print!("{}", out);
+    }
+
+    fn write_grouped(&self, out: &mut dyn fmt::Write, options: &DisplayOptions) -> fmt::Result {
+        writeln!(out, "\n{}", options.section_header(&options.tr_args("list.by_category", &[&self.name])))?;
+        if let Some(prefs) = self.view_prefs {
+            writeln!(out, "{}", options.tr_args("list.saved_view", &[&prefs.describe_in(options.language)]))?;
+        }
+        let groups = self.items_by_category();
+        if groups.is_empty() {
+            return writeln!(out, "{}", options.tr("list.no_items"));
+        }
+        for (category, items) in groups {
+            let header = format!("▸ {} ({})", category.as_deref().unwrap_or(options.tr("list.uncategorized")), items.len());
+            // la première couleur choisie dans le groupe colore l'en-tête
+            match items.iter().find_map(|item| item.category_color) {
+                Some(color) => writeln!(out, "\n{}", options.paint(&header, color.code()))?,
+                None => writeln!(out, "\n{}", header)?,
+            }
+            for item in items {
+                write!(out, "{}", item.render(options))?;
+            }
+        }
+        Ok(())
+    }
+
+    // de la plus urgente à la moins urgente, sans les niveaux vides; dans chaque groupe,
+    // les éléments non terminés d'abord puis les terminés, chacun dans l'ordre habituel
+    pub fn items_by_priority(&self) -> Vec<(Priority, Vec<&TodoItem>)> {
+        let filter = self.view_prefs.and_then(|prefs| prefs.filter).unwrap_or(ItemFilter::All);
+        let key = self.default_sort();
+        [Priority::Critical, Priority::High, Priority::Medium, Priority::Low]
+            .into_iter()
+            .filter_map(|priority| {
+                let mut items: Vec<&TodoItem> =
+                    self.items.iter().filter(|item| item.priority == priority && filter.matches(item)).collect();
+                items.sort_by(|a, b| {
+                    a.status
+                        .is_done()
+                        .cmp(&b.status.is_done())
+                        .then_with(|| b.starred.cmp(&a.starred))
+                        .then_with(|| key.compare(a, b))
+                });
+                (!items.is_empty()).then_some((priority, items))
+            })
+            .collect()
+    }
+
+    pub fn display_by_priority(&self) {
+        let mut out = String::new();
+        let _ = self.write_by_priority(&mut out, &DisplayOptions::from_config());
+        print!("{}", out);
+    }
+
+    fn write_by_priority(&self, out: &mut dyn fmt::Write, options: &DisplayOptions) -> fmt::Result {
+        writeln!(out, "\n{}", options.section_header(&options.tr_args("list.by_priority", &[&self.name])))?;
+        let groups = self.items_by_priority();
+        if groups.is_empty() {
+            return writeln!(out, "{}", options.tr("list.no_items"));
+        }
+        for (priority, items) in groups {
+            let pending = items.iter().filter(|item| !item.status.is_done()).count();
+            let count = options.tr_args("list.priority_group", &[&pending, &items.len()]);
+            let header = format!("▸ {} {} {}", priority.to_symbol(), priority.label_in(options.language), count);
+            writeln!(out, "\n{}", options.paint(&header, priority.color()))?;
+            for (i, item) in items.iter().enumerate() {
+                if i == pending {
+                    writeln!(out, "  {}", options.tr("list.completed_separator"))?;
+                }
+                write!(out, "{}", item.render(options))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn display_archives(&self) {
+        let options = DisplayOptions::from_config();
+        println!("\n{}", options.section_header(&options.tr_args("archives.title", &[&self.name])));
+        println!("{}", options.tr_args("archives.count", &[&self.archived.len()]));
+        println!();
+
+        if self.archived.is_empty() {
+            println!("{}", options.tr("archives.empty"));
+        } else {
+            for item in &self.archived {
+                print!("{}", item.render(&options));
+            }
+        }
+    }
+
+    fn display_items(&self, items: &[TodoItem]) {
+        let mut out = String::new();
+        let _ = self.write_items(&mut out, items, &DisplayOptions::from_config());
+        print!("{}", out);
+    }
+
+    fn write_items(&self, out: &mut dyn fmt::Write, items: &[TodoItem], options: &DisplayOptions) -> fmt::Result {
+        writeln!(out, "\n{}", options.section_header(&self.name))?;
+        writeln!(out, "{}", options.tr_args("list.created", &[&options.format_datetime(self.created_at), &humanize_age_in(self.created_at, options.language)]))?;
+        writeln!(out, "{}", options.tr_args("list.modified", &[&options.format_datetime(self.last_modified), &humanize_age_in(self.last_modified, options.language)]))?;
+        writeln!(out, "{}", options.tr_args("list.count", &[&self.items.len()]))?;
+        let (completed, total, percentage) = self.stats();
+        let percentage_text = format!("{:.0}", percentage);
+        writeln!(out, "{}", options.tr_args("list.progress", &[&completed, &total, &percentage_text, &progress_bar(percentage)]))?;
+        if self.items.iter().any(|item| item.points.is_some()) {
+            writeln!(out, "{}", options.tr_args("list.points", &[&self.remaining_points()]))?;
+        }
+        if items.len() != self.items.len() {
+            writeln!(out, "{}", options.tr_args("list.shown", &[&items.len(), &self.items.len()]))?;
+        }
+        if let Some(prefs) = self.view_prefs {
+            writeln!(out, "{}", options.tr_args("list.saved_view", &[&prefs.describe_in(options.language)]))?;
+        }
+        writeln!(out)?;
+        
+        if items.is_empty() {
+            writeln!(out, "{}", options.tr("list.no_items"))?;
+        } else {
+            // les éléments étoilés passent devant, chaque groupe garde l'ordre demandé
+            for item in items.iter().filter(|item| item.starred).chain(items.iter().filter(|item| !item.starred)) {
+                write!(out, "{}", item.render(options))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// avec les options par défaut, comme pour TodoItem
+impl fmt::Display for TodoList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_view(f, &DisplayOptions::default())
+    }
+}
+
+const RED: &str = "31";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const BLUE: &str = "34";
+const MAGENTA: &str = "35";
+const CYAN: &str = "36";
+
+// couleurs désactivées si NO_COLOR est défini ou si la sortie n'est pas un terminal (redirection vers un fichier)
+fn colors_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && io::stdout().is_terminal()
+    })
+}
+
+
+
+// coupe le texte à `width` caractères, points de suspension compris
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(width.saturating_sub(1)).collect();
+    format!("{}…", kept.trim_end())
+}
+
+// largeur utilisée quand elle ne peut pas être détectée (sortie redirigée, pas de stty)
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+// largeur du terminal: $COLUMNS, sinon `stty size`, sinon 80 colonnes
+pub fn terminal_width() -> usize {
+    if let Some(columns) = env::var("COLUMNS").ok().and_then(|columns| columns.trim().parse().ok())
+        && columns > 0
+    {
+        return columns;
+    }
+    if !io::stdout().is_terminal() {
+        return DEFAULT_TERMINAL_WIDTH;
+    }
+    // stty lit la taille du terminal branché sur son entrée standard
+    Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|size| size.split_whitespace().nth(1)?.parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
+// "=== titre ====..." complété jusqu'à la largeur du terminal
+pub fn section_header(title: &str) -> String {
+    section_header_in(title, terminal_width())
+}
+
+fn section_header_in(title: &str, width: usize) -> String {
+    let used = title.chars().count() + 5;
+    let rule = width.saturating_sub(used).max(3);
+    format!("=== {} {}", title, "=".repeat(rule))
+}
+
+// découpe une ligne en morceaux d'au plus `width` caractères, entre les mots si possible
+fn wrap_text(line: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in line.split_whitespace() {
+        let mut word = word.to_string();
+        // un mot plus long que la ligne est coupé
+        while word.chars().count() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let rest = word.chars().skip(width).collect();
+            lines.push(word.chars().take(width).collect());
+            word = rest;
+        }
+        if current.is_empty() {
+            current = word;
+        } else if current.chars().count() + 1 + word.chars().count() <= width {
+            current.push(' ');
+            current.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut current, word));
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+// barre de 10 caractères, ex: [#######...]
+pub fn progress_bar(percentage: f64) -> String {
+    let filled = ((percentage / 10.0).round() as usize).min(10);
+    format!("[{}{}]", "#".repeat(filled), ".".repeat(10 - filled))
+}
+
+// compare deux éléments par date d'échéance, ceux sans date passent à la fin
+fn compare_due_dates(a: &TodoItem, b: &TodoItem) -> std::cmp::Ordering {
+    match (a.due_date, b.due_date) {
+        (Some(a_date), Some(b_date)) => a_date.cmp(&b_date),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+// répertoire de stockage des listes: $TODOCLI_DATA_DIR, sinon ~/.todocli
+pub fn data_dir() -> PathBuf {
+    if let Ok(dir) = env::var("TODOCLI_DATA_DIR")
+        && !dir.is_empty()
+    {
+        return PathBuf::from(dir);
+    }
+    let home = env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".todocli")
+}
+
+// nom de fichier (sans extension) dérivé du nom de la liste: tout ce qui n'est pas
+// lettre, chiffre ou tiret devient "_", pour qu'un nom comme "a/b" ne sorte pas du répertoire
+pub fn sanitize_name(name: &str) -> String {
+    let mut stem = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_alphanumeric() || c == '-' {
+            stem.push(c);
+        } else if !stem.is_empty() && !stem.ends_with('_') {
+            stem.push('_');
+        }
+    }
+    stem.trim_end_matches('_').to_string()
+}
+
+const CONFIG_FILE: &str = "config.json";
+// nom de la dernière liste sauvegardée, sans extension .json pour ne pas être pris pour une liste
+const LAST_LIST_FILE: &str = "last_list";
+
+// quantité de messages affichés: Quiet masque les confirmations, Verbose ajoute des détails
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn shows_success(self) -> bool {
+        self != Verbosity::Quiet
+    }
+
+    pub fn shows_details(self) -> bool {
+        self == Verbosity::Verbose
+    }
+}
+
+// Files: un fichier <nom>.json par liste; Combined: toutes les listes dans todos.json
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    Files,
+    Combined,
+}
+
+// préférences lues dans <data_dir>/config.json, les valeurs absentes gardent le comportement par défaut
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // sans valeur, la langue vient de la variable d'environnement LANG (français par défaut)
+    pub language: Option<String>,
+    pub date_format: Option<String>,
+    pub due_soon_hours: i64,
+    pub title_width: usize,
+    // menus validés dès l'appui sur une touche, sans Entrée
+    pub single_key_menu: bool,
+    // ligne "Terminé le" sous les éléments terminés; sans elle, seul le ✓ l'indique
+    pub show_completed_at: bool,
+    // liste de capture rapide (`todocli add <titre>`), créée au premier ajout
+    pub default_list: String,
+    // les options -q et -v du mode ligne de commande la remplacent
+    pub verbosity: Verbosity,
+    // changer de stockage ne déplace pas les listes: voir convert_storage
+    pub storage: StorageBackend,
+    // sauvegarde pendant la modification d'une liste toutes les N modifications, 0 pour ne jamais le faire
+    pub autosave_every: u32,
+    // refuse de terminer un élément tant qu'il lui reste des sous-tâches à cocher
+    pub require_subtasks: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            language: None,
+            date_format: None,
+            due_soon_hours: 48,
+            title_width: 60,
+            single_key_menu: false,
+            show_completed_at: true,
+            default_list: "inbox".to_string(),
+            verbosity: Verbosity::Normal,
+            storage: StorageBackend::Files,
+            autosave_every: 5,
+            require_subtasks: true,
+        }
+    }
+}
+
+impl Config {
+    // une langue configurée mais non traduite reste en français, sans consulter LANG
+    pub fn language(&self) -> Language {
+        match &self.language {
+            Some(code) => Language::from_code(code).unwrap_or(Language::Fr),
+            None => env::var("LANG").ok().and_then(|lang| Language::from_code(&lang)).unwrap_or(Language::Fr),
+        }
+    }
+
+    pub fn subtask_rule(&self) -> SubtaskRule {
+        if self.require_subtasks { SubtaskRule::Enforce } else { SubtaskRule::Ignore }
+    }
+
+    pub fn is_english(&self) -> bool {
+        self.language() == Language::En
+    }
+
+    pub fn date_format(&self) -> &str {
+        match &self.date_format {
+            Some(format) => format,
+            None if self.is_english() => "%m/%d/%Y",
+            None => "%d/%m/%Y",
+        }
+    }
+
+    // la variable d'environnement TODOCLI_DUE_SOON_HOURS a priorité sur le fichier
+    pub fn due_soon_window(&self) -> Duration {
+        let hours = env::var("TODOCLI_DUE_SOON_HOURS")
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(self.due_soon_hours);
+        Duration::hours(hours.max(0))
+    }
+
+    // la variable d'environnement TODOCLI_DEFAULT_LIST a priorité sur le fichier
+    pub fn default_list_name(&self) -> String {
+        env::var("TODOCLI_DEFAULT_LIST")
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !sanitize_name(name).is_empty())
+            .unwrap_or_else(|| self.default_list.clone())
+    }
+
+    pub fn datetime_format(&self) -> String {
+        datetime_format_in(self.date_format(), self.language())
+    }
+}
+
+fn datetime_format_in(date_format: &str, language: Language) -> String {
+    let connector = if language == Language::En { "at" } else { "à" };
+    format!("{} {} %H:%M", date_format, connector)
+}
+
+// ce dont dépend l'affichage des listes et des éléments: from_config à l'écran,
+// Default (français, JJ/MM/AAAA, 80 colonnes, sans couleur) pour Display et les tests
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    pub language: Language,
+    pub date_format: String,
+    // titre tronqué au-delà, sauf dans la vue détaillée
+    pub title_width: usize,
+    pub show_completed_at: bool,
+    // largeur des titres de section et des descriptions
+    pub width: usize,
+    pub colors: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        let defaults = Config::default();
+        DisplayOptions {
+            language: Language::Fr,
+            date_format: "%d/%m/%Y".to_string(),
+            title_width: defaults.title_width,
+            show_completed_at: defaults.show_completed_at,
+            width: DEFAULT_TERMINAL_WIDTH,
+            colors: false,
+        }
+    }
+}
+
+impl DisplayOptions {
+    pub fn from_config() -> Self {
+        DisplayOptions {
+            language: config().language(),
+            date_format: config().date_format().to_string(),
+            title_width: config().title_width,
+            show_completed_at: config().show_completed_at,
+            width: terminal_width(),
+            colors: colors_enabled(),
+        }
+    }
+
+    fn tr<'a>(&self, key: &'a str) -> &'a str {
+        i18n::tr_in(self.language, key)
+    }
+
+    fn tr_args(&self, key: &str, args: &[&dyn fmt::Display]) -> String {
+        i18n::tr_args_in(self.language, key, args)
+    }
+
+    fn format_date(&self, date: NaiveDate) -> String {
+        date.format(&self.date_format).to_string()
+    }
+
+    fn format_datetime(&self, datetime: DateTime<Utc>) -> String {
+        datetime.format(&datetime_format_in(&self.date_format, self.language)).to_string()
+    }
+
+    fn section_header(&self, title: &str) -> String {
+        section_header_in(title, self.width)
+    }
+
+    fn paint(&self, text: &str, color: &str) -> String {
+        if self.colors {
+            format!("\x1b[{}m{}\x1b[0m", color, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+fn load_config() -> Config {
+    let path = data_dir().join(CONFIG_FILE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Config::default();
+    };
+    match serde_json::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Configuration '{}' ignorée: {}", path.display(), e);
+            Config::default()
+        }
+    }
+}
+
+pub fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(load_config)
+}
+
+// texte de l'interface dans la langue choisie, voir i18n.rs
+pub fn tr(key: &str) -> &str {
+    i18n::tr_in(config().language(), key)
+}
+
+// même chose pour un message avec des valeurs ("{}" dans le catalogue)
+pub fn tr_args(key: &str, args: &[&dyn fmt::Display]) -> String {
+    i18n::tr_args_in(config().language(), key, args)
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(config().date_format()).to_string()
+}
+
+pub fn format_datetime(datetime: DateTime<Utc>) -> String {
+    datetime.format(&config().datetime_format()).to_string()
+}
+
+// 90 -> "1h30", 45 -> "45 min"
+pub fn format_minutes(minutes: i64) -> String {
+    if minutes < 60 {
+        format!("{} min", minutes)
+    } else {
+        format!("{}h{:02}", minutes / 60, minutes % 60)
+    }
+}
+
+// durée écoulée depuis `ts`, ex: "il y a 3 jours"
+pub fn humanize_age(ts: DateTime<Utc>) -> String {
+    humanize_age_in(ts, config().language())
+}
+
+pub fn humanize_age_in(ts: DateTime<Utc>, language: Language) -> String {
+    let age = Utc::now() - ts;
+    let (count, unit) = if age.num_minutes() < 1 {
+        return i18n::tr_in(language, "age.just_now").to_string();
+    } else if age.num_hours() < 1 {
+        (age.num_minutes(), "minute")
+    } else if age.num_days() < 1 {
+        (age.num_hours(), "hour")
+    } else if age.num_days() < 30 {
+        (age.num_days(), "day")
+    } else if age.num_days() < 365 {
+        (age.num_days() / 30, "month")
+    } else {
+        (age.num_days() / 365, "year")
+    };
+    let plural = if count > 1 { "s" } else { "" };
+    let unit = i18n::tr_in(language, &format!("age.{}{}", unit, plural)).to_string();
+    i18n::tr_args_in(language, "age.ago", &[&count, &unit])
+}
+
+// "%d/%m/%Y" -> "JJ/MM/AAAA", pour les messages de saisie
+pub fn date_format_hint(format: &str) -> String {
+    format.replace("%d", "JJ").replace("%m", "MM").replace("%Y", "AAAA")
+}
+
+// date d'échéance saisie: aujourd'hui, demain, après-demain, +3j (jours), +2s (semaines),
+// +1m (mois), ou une date au format donné (en général config().date_format());
+// None si la saisie n'est pas reconnue
+pub fn parse_due(input: &str, date_format: &str) -> Option<NaiveDate> {
+    parse_due_from(input, Local::now().date_naive(), date_format)
+}
+
+pub fn parse_due_from(input: &str, today: NaiveDate, date_format: &str) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase().replace('’', "'");
+    match input.as_str() {
+        "aujourd'hui" | "aujourdhui" => return Some(today),
+        "demain" => return today.succ_opt(),
+        "après-demain" | "apres-demain" => return today.checked_add_days(chrono::Days::new(2)),
+        _ => {}
+    }
+    if let Some(offset) = input.strip_prefix('+') {
+        let unit_start = offset.find(|c: char| !c.is_ascii_digit())?;
+        let count: u32 = offset[..unit_start].parse().ok()?;
+        return match offset[unit_start..].trim() {
+            "j" => today.checked_add_days(chrono::Days::new(count.into())),
+            "s" => today.checked_add_days(chrono::Days::new(u64::from(count) * 7)),
+            "m" => today.checked_add_months(chrono::Months::new(count)),
+            _ => None,
+        };
+    }
+    NaiveDate::parse_from_str(&input, date_format).ok()
+}
+
+pub fn list_path(name: &str) -> PathBuf {
+    let path = data_dir().join(format!("{}.json", sanitize_name(name)));
+    // les listes créées avant sanitize_name gardent leur ancien nom de fichier
+    let legacy_stem = name.replace(" ", "_").to_lowercase();
+    if !path.exists() && !legacy_stem.contains(['/', '\\']) && legacy_stem != ".." {
+        let legacy_path = data_dir().join(format!("{}.json", legacy_stem));
+        if legacy_path.exists() {
+            return legacy_path;
+        }
+    }
+    path
+}
+
+const COMBINED_FILE: &str = "todos.json";
+
+pub fn combined_path() -> PathBuf {
+    data_dir().join(COMBINED_FILE)
+}
+
+// fichier qui contient la liste avec le stockage choisi: le sien, ou todos.json
+pub fn storage_path(name: &str) -> PathBuf {
+    match config().storage {
+        StorageBackend::Files => list_path(name),
+        StorageBackend::Combined => combined_path(),
+    }
+}
+
+// contenu de todos.json: nom de fichier de la liste (voir sanitize_name) -> liste au format habituel
+type CombinedLists = BTreeMap<String, serde_json::Value>;
+
+// un fichier absent équivaut à aucune liste
+fn read_combined() -> Result<CombinedLists, Box<dyn std::error::Error>> {
+    match fs::read_to_string(combined_path()) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(CombinedLists::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_combined(lists: &CombinedLists) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(data_dir())?;
+    write_atomically(&combined_path(), &serde_json::to_string_pretty(lists)?)?;
+    Ok(())
+}
+
+// écriture dans un fichier temporaire puis renommage: en cas d'arrêt brutal,
+// on garde soit l'ancien fichier complet, soit le nouveau, jamais un fichier tronqué
+fn write_atomically(path: &std::path::Path, content: &str) -> io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
+
+// renvoie le chemin du fichier écrit
+pub fn save_todo_list(todo_list: &mut TodoList) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    fs::create_dir_all(data_dir())?;
+    let path = match config().storage {
+        StorageBackend::Files => {
+            let path = list_path(&todo_list.name);
+            write_atomically(&path, &serde_json::to_string_pretty(todo_list)?)?;
+            path
+        }
+        // relu juste avant l'écriture: les autres listes gardent leur dernière version
+        StorageBackend::Combined => {
+            let mut lists = read_combined()?;
+            lists.insert(sanitize_name(&todo_list.name), serde_json::to_value(&*todo_list)?);
+            write_combined(&lists)?;
+            combined_path()
+        }
+    };
+    // le pointeur n'est qu'un raccourci: son échec ne doit pas faire échouer la sauvegarde
+    let _ = fs::write(data_dir().join(LAST_LIST_FILE), &todo_list.name);
+    todo_list.unsaved_changes = 0;
+    append_history(&todo_list.name, &todo_list.history)?;
+    todo_list.history.clear();
+    Ok(path)
+}
+
+// journal des opérations d'une liste, à côté de son fichier JSON
+pub fn history_path(name: &str) -> PathBuf {
+    list_path(name).with_extension("history.jsonl")
+}
+
+// avant d'écraser une liste: son historique est renommé "<nom>.history.<date>.jsonl" pour que
+// la nouvelle liste ne reprenne pas celui de l'ancienne; renvoie le nouveau chemin s'il y en avait un
+pub fn rotate_history(name: &str) -> io::Result<Option<PathBuf>> {
+    let history = history_path(name);
+    if !history.exists() {
+        return Ok(None);
+    }
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
+    let rotated = list_path(name).with_extension(format!("history.{}.jsonl", timestamp));
+    fs::rename(&history, &rotated)?;
+    Ok(Some(rotated))
+}
+
+// une entrée JSON par ligne: on ajoute à la fin sans relire le fichier
+fn append_history(name: &str, entries: &[HistoryEntry]) -> Result<(), Box<dyn std::error::Error>> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry)?);
+        lines.push('\n');
+    }
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(history_path(name))?;
+    file.write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+// les lignes illisibles (fichier modifié à la main, écriture interrompue) sont ignorées
+pub fn load_history(name: &str) -> Vec<HistoryEntry> {
+    let Ok(content) = fs::read_to_string(history_path(name)) else {
+        return Vec::new();
+    };
+    content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect()
+}
+
+pub fn last_list_name() -> Option<String> {
+    let name = fs::read_to_string(data_dir().join(LAST_LIST_FILE)).ok()?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+pub fn load_todo_list(name: &str) -> Result<TodoList, Box<dyn std::error::Error>> {
+    let value: serde_json::Value = match config().storage {
+        StorageBackend::Files => serde_json::from_str(&fs::read_to_string(list_path(name))?)?,
+        StorageBackend::Combined => read_combined()?.remove(&sanitize_name(name)).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("la liste '{}' n'est pas dans {}", name, COMBINED_FILE))
+        })?,
+    };
+    Ok(migrate(value)?)
+}
+
+pub fn list_exists(name: &str) -> bool {
+    match config().storage {
+        StorageBackend::Files => list_path(name).exists(),
+        StorageBackend::Combined => read_combined().is_ok_and(|lists| lists.contains_key(&sanitize_name(name))),
+    }
+}
+
+// regroupe toutes les listes dans todos.json (Combined) ou les répartit en un fichier par liste (Files),
+// renvoie le nombre de listes déplacées; rien n'est écrit si une liste existe déjà à l'arrivée, et
+// les anciens fichiers ne sont supprimés qu'une fois les nouveaux écrits. config.json n'est pas modifié.
+pub fn convert_storage(to: StorageBackend) -> Result<usize, Box<dyn std::error::Error>> {
+    let conflict = |name: &str| io::Error::new(io::ErrorKind::AlreadyExists, format!("la liste '{}' existe déjà à l'arrivée", name));
+    match to {
+        StorageBackend::Combined => {
+            let mut lists = read_combined()?;
+            let mut moved = Vec::new();
+            for name in list_files_names() {
+                let path = list_path(&name);
+                let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+                if lists.insert(sanitize_name(&name), value).is_some() {
+                    return Err(conflict(&name).into());
+                }
+                moved.push(path);
+            }
+            write_combined(&lists)?;
+            for path in &moved {
+                fs::remove_file(path)?;
+            }
+            Ok(moved.len())
+        }
+        StorageBackend::Files => {
+            let lists = read_combined()?;
+            if let Some(stem) = lists.keys().find(|stem| data_dir().join(format!("{}.json", stem)).exists()) {
+                return Err(conflict(stem).into());
+            }
+            for (stem, value) in &lists {
+                write_atomically(&data_dir().join(format!("{}.json", stem)), &serde_json::to_string_pretty(value)?)?;
+            }
+            if combined_path().exists() {
+                fs::remove_file(combined_path())?;
+            }
+            Ok(lists.len())
+        }
+    }
+}
+
+// liste vide (non sauvegardée) si elle n'existe pas encore
+pub fn load_or_create_todo_list(name: &str) -> Result<TodoList, Box<dyn std::error::Error>> {
+    if list_exists(name) {
+        load_todo_list(name)
+    } else {
+        Ok(TodoList::new(name.to_string()))
+    }
+}
+
+// à augmenter à chaque changement de forme du fichier, avec l'étape correspondante dans migrate
+//   1: "status" remplace le booléen "completed"
+//   2: compteur "next_id" et "position" des éléments
+pub const SCHEMA_VERSION: u32 = 2;
+
+// met à niveau le JSON d'une liste écrit par une version antérieure, puis le désérialise;
+// un fichier plus récent est lu tel quel (les champs inconnus sont ignorés)
+pub fn migrate(mut value: serde_json::Value) -> Result<TodoList, serde_json::Error> {
+    let version = value.get("schema_version").and_then(|version| version.as_u64()).unwrap_or(0);
+    if version < 1 {
+        migrate_completed_flag(&mut value);
+    }
+    let mut todo_list: TodoList = serde_json::from_value(value)?;
+    if version < 2 {
+        todo_list.backfill_next_id();
+        todo_list.backfill_positions();
+    }
+    todo_list.schema_version = todo_list.schema_version.max(SCHEMA_VERSION);
+    Ok(todo_list)
+}
+
+// les anciens fichiers n'ont qu'un booléen "completed": true devient Terminée, false À faire
+fn migrate_completed_flag(value: &mut serde_json::Value) {
+    for key in ["items", "archived"] {
+        let Some(items) = value.get_mut(key).and_then(|items| items.as_array_mut()) else {
+            continue;
+        };
+        for item in items.iter_mut().filter_map(|item| item.as_object_mut()) {
+            if item.contains_key("status") {
+                continue;
+            }
+            let completed = item.remove("completed").and_then(|completed| completed.as_bool()).unwrap_or(false);
+            let status = if completed { TaskStatus::Terminee } else { TaskStatus::Afaire };
+            item.insert("status".to_string(), serde_json::json!(status));
+        }
+    }
+}
+
+// copie une liste sous un nouveau nom, tous les éléments repartent à "À faire"
+pub fn duplicate_list(source: &str, new_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut todo_list = load_todo_list(source)?;
+    todo_list.name = new_name.to_string();
+    todo_list.archived.clear();
+    for item in &mut todo_list.items {
+        item.mark_incomplete();
+        for subtask in &mut item.subtasks {
+            subtask.done = false;
+        }
+        item.notes.clear();
+    }
+    todo_list.created_at = Utc::now();
+    todo_list.touch();
+    save_todo_list(&mut todo_list)?;
+    Ok(())
+}
+
+// un JSON valide qui n'a pas la forme d'une liste (autre outil, fichier de config...) est ignoré;
+// un JSON illisible est gardé pour que l'utilisateur puisse le récupérer
+fn looks_like_todo_list(path: &std::path::Path) -> bool {
+    let Ok(content) = fs::read_to_string(path) else {
+        return false;
+    };
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(value) => value.get("name").is_some() && value.get("items").is_some(),
+        Err(_) => true,
+    }
+}
+
+// déplace un élément vers une autre liste, où il reçoit un nouvel ID; la destination est
+// sauvegardée en premier pour qu'une erreur ne fasse jamais perdre l'élément.
+// Renvoie take_compaction de la destination, si lui attribuer un ID l'a renumérotée
+pub fn transfer_item(from: &str, id: u32, to: &str) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+    if sanitize_name(from) == sanitize_name(to) {
+        return Err("la liste de destination doit être différente de la liste d'origine".into());
+    }
+    let mut source = load_todo_list(from)?;
+    let mut destination = load_todo_list(to)?;
+    let Some(index) = source.items.iter().position(|item| item.id == id) else {
+        return Err(format!("aucun élément avec l'ID {} dans '{}'", id, source.name).into());
+    };
+
+    source.record(HistoryAction::Remove, id);
+    let mut item = source.items.remove(index);
+    item.id = destination.allocate_id();
+    let new_id = item.id;
+    destination.items.push(item);
+    destination.record(HistoryAction::Add, new_id);
+    destination.touch();
+    source.touch();
+    save_todo_list(&mut destination)?;
+    save_todo_list(&mut source)?;
+    Ok(destination.take_compaction())
+}
+
+// toutes les listes réunies dans un seul fichier, pour backup_all et read_backup
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub created_at: DateTime<Utc>,
+    pub lists: BTreeMap<String, TodoList>,
+}
+
+pub fn backup_all(dest: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut lists = BTreeMap::new();
+    for list_name in list_saved_todo_lists() {
+        let todo_list = load_todo_list(&list_name)?;
+        lists.insert(todo_list.name.clone(), todo_list);
+    }
+    let backup = Backup { created_at: Utc::now(), lists };
+    write_atomically(std::path::Path::new(dest), &serde_json::to_string_pretty(&backup)?)?;
+    Ok(())
+}
+
+// listes contenues dans une sauvegarde, sans rien écrire: l'appelant verrouille
+// les listes concernées puis enregistre chacune d'elles, en remplaçant celles qui portent le même nom
+pub fn read_backup(src: &str) -> Result<Vec<TodoList>, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(src)?;
+    let backup: serde_json::Value = serde_json::from_str(&content)?;
+    let lists = backup
+        .get("lists")
+        .and_then(|lists| lists.as_object())
+        .ok_or("sauvegarde invalide: champ 'lists' manquant")?;
+    // les listes sauvegardées par une version antérieure passent par la même migration
+    let lists = lists.values().cloned().map(migrate).collect::<Result<_, _>>()?;
+    Ok(lists)
+}
+
+// verrou consultatif posé à côté du fichier pendant qu'une liste est en cours de modification
+pub fn lock_path(name: &str) -> PathBuf {
+    list_path(name).with_extension("json.lock")
+}
+
+// contenu du fichier de verrou: qui l'a posé et depuis quand
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct LockHolder {
+    pub pid: Option<u32>,
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl LockHolder {
+    // Some(true) si le processus n'existe plus (verrou laissé par un arrêt brutal);
+    // None quand on ne peut pas le savoir (pas de /proc, ou verrou illisible)
+    pub fn is_stale(&self) -> Option<bool> {
+        let pid = self.pid?;
+        if !std::path::Path::new("/proc/self").exists() {
+            return None;
+        }
+        Some(!std::path::Path::new(&format!("/proc/{}", pid)).exists())
+    }
+}
+
+#[derive(Debug)]
+pub enum LockError {
+    Locked(LockHolder),
+    Io(io::Error),
+}
+
+impl fmt::Display for LockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LockError::Locked(holder) => {
+                write!(f, "{}", tr("lock.locked"))?;
+                if let Some(pid) = holder.pid {
+                    write!(f, "{}", tr_args("lock.by_pid", &[&pid]))?;
+                }
+                if let Some(since) = holder.since {
+                    write!(f, "{}", tr_args("lock.since", &[&format_datetime(since)]))?;
+                }
+                Ok(())
+            }
+            LockError::Io(e) => write!(f, "{}", tr_args("lock.io", &[e])),
+        }
+    }
+}
+
+impl std::error::Error for LockError {}
+
+// supprime le fichier de verrou à la fin de l'édition (y compris en cas d'erreur)
+#[derive(Debug)]
+pub struct ListLock {
+    path: PathBuf,
+}
+
+impl Drop for ListLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// create_new échoue si le verrou existe déjà, même si deux instances essaient en même temps;
+// avec force, un verrou existant est remplacé (à réserver aux verrous périmés)
+pub fn lock_list(name: &str, force: bool) -> Result<ListLock, LockError> {
+    let path = lock_path(name);
+    // une liste peut être verrouillée avant d'être créée, dossier de données compris
+    fs::create_dir_all(data_dir()).map_err(LockError::Io)?;
+    if force {
+        let _ = fs::remove_file(&path);
+    }
+    match fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut file) => {
+            let holder = LockHolder { pid: Some(std::process::id()), since: Some(Utc::now()) };
+            let content = serde_json::to_string(&holder).unwrap_or_default();
+            file.write_all(content.as_bytes()).map_err(LockError::Io)?;
+            Ok(ListLock { path })
+        }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            let holder = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+            Err(LockError::Locked(holder))
+        }
+        Err(e) => Err(LockError::Io(e)),
+    }
+}
+
+const TRASH_DIR: &str = "trash";
+
+pub fn trash_dir() -> PathBuf {
+    data_dir().join(TRASH_DIR)
+}
+
+// déplace la liste (et son historique) dans la corbeille sous "<nom>_<date>", renvoie ce nom
+// avec dry_run, rien n'est déplacé: on renvoie seulement le nom qui serait utilisé
+pub fn trash_list(name: &str, dry_run: bool) -> io::Result<String> {
+    let path = list_path(name);
+    let stem = match config().storage {
+        StorageBackend::Files => path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string(),
+        StorageBackend::Combined => sanitize_name(name),
+    };
+    if dry_run {
+        if !list_exists(name) {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("la liste '{}' n'existe pas", name)));
+        }
+    } else {
+        fs::create_dir_all(trash_dir())?;
+    }
+
+    // deux suppressions dans la même seconde ne doivent pas s'écraser
+    let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut entry = format!("{}_{}", stem, timestamp);
+    let mut attempt = 1;
+    while trash_dir().join(format!("{}.json", entry)).exists() {
+        attempt += 1;
+        entry = format!("{}_{}-{}", stem, timestamp, attempt);
+    }
+    if dry_run {
+        return Ok(entry);
+    }
+
+    let destination = trash_dir().join(format!("{}.json", entry));
+    match config().storage {
+        StorageBackend::Files => fs::rename(&path, destination)?,
+        // la corbeille garde un fichier par liste, comme avec le stockage par fichiers
+        StorageBackend::Combined => {
+            let mut lists = read_combined().map_err(|e| io::Error::other(e.to_string()))?;
+            let value = lists
+                .remove(&stem)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("la liste '{}' n'existe pas", name)))?;
+            fs::write(destination, serde_json::to_string_pretty(&value)?)?;
+            write_combined(&lists).map_err(|e| io::Error::other(e.to_string()))?;
+        }
+    }
+    let history = history_path(name);
+    if history.exists() {
+        let _ = fs::rename(history, trash_dir().join(format!("{}.history.jsonl", entry)));
+    }
+    Ok(entry)
+}
+
+// noms des listes de la corbeille, les plus récemment supprimées en premier
+pub fn list_trash() -> Vec<String> {
+    let mut entries: Vec<String> = fs::read_dir(trash_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".json").map(str::to_string))
+        .collect();
+    entries.sort_by(|a, b| {
+        let date = |entry: &str| entry.rsplit_once('_').map(|(_, date)| date.to_string()).unwrap_or_default();
+        date(b).cmp(&date(a)).then_with(|| a.cmp(b))
+    });
+    entries
+}
+
+// remet une entrée de list_trash à sa place, sans écraser une liste qui aurait été recréée depuis
+pub fn restore_from_trash(entry: &str) -> io::Result<PathBuf> {
+    let source = trash_dir().join(format!("{}.json", entry));
+    if !source.exists() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("'{}' n'est pas dans la corbeille", entry)));
+    }
+    let stem = entry.rsplit_once('_').map_or(entry, |(stem, _)| stem);
+    let destination = data_dir().join(format!("{}.json", stem));
+    if list_exists(stem) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("une liste '{}' existe déjà; supprimez-la ou renommez-la d'abord", stem),
+        ));
+    }
+
+    let restored = match config().storage {
+        StorageBackend::Files => {
+            fs::rename(&source, &destination)?;
+            destination.clone()
+        }
+        StorageBackend::Combined => {
+            let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(&source)?)?;
+            let mut lists = read_combined().map_err(|e| io::Error::other(e.to_string()))?;
+            lists.insert(stem.to_string(), value);
+            write_combined(&lists).map_err(|e| io::Error::other(e.to_string()))?;
+            fs::remove_file(&source)?;
+            combined_path()
+        }
+    };
+    let history = trash_dir().join(format!("{}.history.jsonl", entry));
+    let history_destination = destination.with_extension("history.jsonl");
+    if history.exists() && !history_destination.exists() {
+        let _ = fs::rename(history, history_destination);
+    }
+    Ok(restored)
+}
+
+// supprime définitivement le contenu de la corbeille, renvoie le nombre de listes effacées
+pub fn empty_trash() -> io::Result<usize> {
+    let count = list_trash().len();
+    if trash_dir().exists() {
+        fs::remove_dir_all(trash_dir())?;
+    }
+    Ok(count)
+}
+
+pub fn list_saved_todo_lists() -> Vec<String> {
+    match config().storage {
+        StorageBackend::Files => list_files_names(),
+        StorageBackend::Combined => match read_combined() {
+            Ok(lists) => lists.keys().map(|stem| stem.replace("_", " ")).collect(),
+            Err(e) => {
+                eprintln!("Fichier '{}' illisible: {}", combined_path().display(), e);
+                Vec::new()
+            }
+        },
+    }
+}
+
+// listes enregistrées chacune dans son fichier, quel que soit le stockage choisi
+fn list_files_names() -> Vec<String> {
+    let mut lists = Vec::new();
+    if let Ok(entries) = fs::read_dir(data_dir()) {
+        for entry in entries.flatten() {
+            if let Some(filename) = entry.file_name().to_str()
+                && filename.ends_with(".json")
+                && filename != CONFIG_FILE
+                && filename != COMBINED_FILE
+                && looks_like_todo_list(&entry.path())
+            {
+                let name = filename.replace(".json", "").replace("_", " ");
+                lists.push(name);
+            }
+        }
+    }
+    // l'ordre du répertoire n'est pas garanti: trié, le numéro d'une liste reste le même
+    // d'un lancement à l'autre (menu de choix, `todocli open <n>`)
+    lists.sort();
+    lists
+}
+
+// nombre de fichiers .json du répertoire et leur date de modification la plus récente, sans les lire:
+// permet de savoir si un résultat calculé à partir des listes est encore à jour
+pub fn data_dir_fingerprint() -> (usize, Option<SystemTime>) {
+    let mut count = 0;
+    let mut latest = None;
+    for entry in fs::read_dir(data_dir()).into_iter().flatten().flatten() {
+        if entry.file_name().to_str().is_some_and(|filename| filename.ends_with(".json")) {
+            count += 1;
+            let modified = entry.metadata().and_then(|metadata| metadata.modified()).ok();
+            latest = latest.max(modified);
+        }
+    }
+    (count, latest)
+}
+
+// au-delà, une faute de frappe comme "1-99999999" bloquerait le programme
+const MAX_RANGE_LEN: u32 = 10_000;
+
+// "2-5,8" -> [2, 3, 4, 5, 8], None si la saisie est invalide
+pub fn parse_id_ranges(input: &str) -> Option<Vec<u32>> {
+    let mut ids = Vec::new();
+    for part in input.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        if let Some((start, end)) = part.split_once('-') {
+            let start: u32 = start.trim().parse().ok()?;
+            let end: u32 = end.trim().parse().ok()?;
+            let (start, end) = (start.min(end), start.max(end));
+            if end - start >= MAX_RANGE_LEN {
+                return None;
+            }
+            ids.extend(start..=end);
+        } else {
+            ids.push(part.parse().ok()?);
+        }
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() { None } else { Some(ids) }
+}
+
+// par défaut: recherche littérale, sans tenir compte de la casse
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchOptions {
+    pub regex: bool,
+    pub case_sensitive: bool,
+}
+
+// recherche dans le titre et la description des éléments de toutes les listes sauvegardées
+// un motif invalide (ou trop complexe pour un des textes) est une erreur plutôt qu'une recherche sans résultat
+pub fn search_all(query: &str, options: SearchOptions) -> Result<Vec<(String, TodoItem)>, PatternError> {
+    let pattern = if options.regex {
+        Pattern::new(query, options.case_sensitive)?
+    } else {
+        Pattern::literal(query, options.case_sensitive)
+    };
+    let mut results = Vec::new();
+    for list_name in list_saved_todo_lists() {
+        let Ok(todo_list) = load_todo_list(&list_name) else {
+            continue;
+        };
+        for item in todo_list.items {
+            let in_title = pattern.is_match(&item.title)?;
+            let in_description = match &item.description {
+                Some(description) => pattern.is_match(description)?,
+                None => false,
+            };
+            if in_title || in_description {
+                results.push((todo_list.name.clone(), item));
+            }
+        }
+    }
+    Ok(results)
+}
+
+// éléments non terminés dont l'échéance tombe entre aujourd'hui et maintenant + within
+pub fn due_soon(within: Duration) -> Vec<(String, TodoItem)> {
+    let today = Local::now().date_naive();
+    let limit = (Local::now() + within).date_naive();
+    let mut results = Vec::new();
+    for list_name in list_saved_todo_lists() {
+        let Ok(todo_list) = load_todo_list(&list_name) else {
+            continue;
+        };
+        for item in todo_list.items {
+            if !item.status.is_done()
+                && item.due_date.is_some_and(|due_date| due_date >= today && due_date <= limit)
+            {
+                results.push((todo_list.name.clone(), item));
+            }
+        }
+    }
+    results.sort_by(|(_, a), (_, b)| compare_due_dates(a, b));
+    results
+}
+
+// éléments non terminés dont l'échéance est dépassée, les plus en retard en premier
+pub fn overdue_all() -> Vec<(String, TodoItem)> {
+    let mut results = Vec::new();
+    for list_name in list_saved_todo_lists() {
+        let Ok(todo_list) = load_todo_list(&list_name) else {
+            continue;
+        };
+        for item in todo_list.items {
+            if item.is_overdue() {
+                results.push((todo_list.name.clone(), item));
+            }
+        }
+    }
+    results.sort_by(|(_, a), (_, b)| compare_due_dates(a, b));
+    results
+}
+
+// la plus vieille tâche non terminée de toutes les listes, avec le nom de sa liste
+pub fn oldest_incomplete_all() -> Option<(String, TodoItem)> {
+    list_saved_todo_lists()
+        .iter()
+        .filter_map(|list_name| load_todo_list(list_name).ok())
+        .filter_map(|todo_list| {
+            let item = todo_list.oldest_incomplete()?.clone();
+            Some((todo_list.name, item))
+        })
+        .min_by_key(|(_, item)| item.created_at)
+}
+
+// bilan du jour pour toutes les listes: en retard, à faire aujourd'hui, terminé aujourd'hui;
+// texte sans couleur, pour être lu dans un mail de cron ou un terminal
+pub fn daily_review(now: DateTime<Utc>) -> String {
+    let lists: Vec<TodoList> = list_saved_todo_lists().iter().filter_map(|list_name| load_todo_list(list_name).ok()).collect();
+    review_report(&lists, now, config().language(), config().date_format())
+}
+
+fn review_report(lists: &[TodoList], now: DateTime<Utc>, language: Language, date_format: &str) -> String {
+    let today = now.with_timezone(&Local).date_naive();
+    let mut overdue = Vec::new();
+    let mut due_today = Vec::new();
+    let mut completed = Vec::new();
+    for todo_list in lists {
+        for item in &todo_list.items {
+            match item.due_date {
+                Some(due_date) if !item.status.is_done() && due_date < today => overdue.push((&todo_list.name, item)),
+                Some(due_date) if !item.status.is_done() && due_date == today => due_today.push((&todo_list.name, item)),
+                _ => {}
+            }
+        }
+        // les éléments archivés dans la journée comptent aussi
+        for item in todo_list.items.iter().chain(&todo_list.archived) {
+            if item.status.is_done()
+                && item.completed_at.is_some_and(|completed_at| completed_at.with_timezone(&Local).date_naive() == today)
+            {
+                completed.push((&todo_list.name, item));
+            }
+        }
+    }
+    overdue.sort_by(|(_, a), (_, b)| compare_due_dates(a, b));
+    due_today.sort_by_key(|(_, item)| std::cmp::Reverse(item.priority as u8));
+    completed.sort_by_key(|(_, item)| item.completed_at);
+
+    let mut report = format!("{}\n", i18n::tr_args_in(language, "review.title", &[&today.format(date_format)]));
+    let mut section = |heading: &str, items: &[(&String, &TodoItem)], detail: &dyn Fn(&TodoItem) -> String| {
+        report.push_str(&format!("\n{}\n", i18n::tr_args_in(language, heading, &[&items.len()])));
+        if items.is_empty() {
+            report.push_str(&format!("  {}\n", i18n::tr_in(language, "review.nothing")));
+        }
+        for (list_name, item) in items {
+            report.push_str(&format!("  [{}] {} - {}{}\n", list_name, item.id, item.title, detail(item)));
+        }
+    };
+    section("review.overdue", &overdue, &|item| match item.due_date {
+        Some(due_date) => {
+            format!(" {}", i18n::tr_args_in(language, "review.late", &[&due_date.format(date_format), &(today - due_date).num_days()]))
+        }
+        None => String::new(),
+    });
+    section("review.due_today", &due_today, &|item| format!(" {}", item.priority.label_in(language)));
+    section("review.completed_today", &completed, &|_| String::new());
+    report
+}
+
+// groupes de listes (au moins deux) dont les éléments ont les mêmes titres et le même état
+// d'avancement, dans le même ordre; les listes vides sont ignorées
+pub fn find_duplicate_lists() -> Vec<Vec<String>> {
+    let mut groups: BTreeMap<Vec<(String, bool)>, Vec<String>> = BTreeMap::new();
+    for list_name in list_saved_todo_lists() {
+        let Ok(todo_list) = load_todo_list(&list_name) else {
+            continue;
+        };
+        if todo_list.items.is_empty() {
+            continue;
+        }
+        let content = todo_list.items.iter().map(|item| (item.title.clone(), item.status.is_done())).collect();
+        groups.entry(content).or_default().push(list_name);
+    }
+    groups
+        .into_values()
+        .filter(|names| names.len() > 1)
+        .map(|mut names| {
+            names.sort();
+            names
+        })
+        .collect()
+}
+
+// la tâche à faire maintenant: non terminée et pas en attente de préférence, puis la plus
+// prioritaire, l'échéance la plus proche, une tâche déjà commencée, et enfin la plus ancienne
+pub fn next_task(list: &TodoList) -> Option<&TodoItem> {
+    let waiting = |item: &TodoItem| item.status == TaskStatus::EnAttente;
+    let started = |item: &TodoItem| item.status == TaskStatus::EnCours;
+    list.items.iter().filter(|item| !item.status.is_done()).min_by(|a, b| {
+        waiting(a)
+            .cmp(&waiting(b))
+            .then_with(|| SortKey::Priority.compare(a, b))
+            .then_with(|| started(b).cmp(&started(a)))
+            .then_with(|| a.created_at.cmp(&b.created_at))
+            .then_with(|| a.id.cmp(&b.id))
+    })
+}
+
+// période de regroupement de completion_histogram
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bucket {
+    Day,
+    Week,
+}
+
+impl Bucket {
+    // début de la période qui contient la date (le lundi pour une semaine)
+    fn start(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Bucket::Day => date,
+            Bucket::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        }
+    }
+
+    fn label(&self, start: NaiveDate, language: Language, date_format: &str) -> String {
+        let date = start.format(date_format);
+        match self {
+            Bucket::Day => date.to_string(),
+            Bucket::Week => i18n::tr_args_in(language, "chart.week_of", &[&date]),
+        }
+    }
+}
+
+// nombre d'éléments terminés par période (archives comprises), du plus ancien au plus récent;
+// les périodes sans complétion ne figurent pas
+pub fn completion_histogram(list: &TodoList, bucket: Bucket, language: Language, date_format: &str) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for item in list.items.iter().chain(&list.archived) {
+        if !item.status.is_done() {
+            continue;
+        }
+        if let Some(completed_at) = item.completed_at {
+            let date = completed_at.with_timezone(&Local).date_naive();
+            *counts.entry(bucket.start(date)).or_default() += 1;
+        }
+    }
+    counts.into_iter().map(|(start, count)| (bucket.label(start, language, date_format), count)).collect()
+}
+
+// chiffres clés d'une liste pour le tableau de bord
+#[derive(Debug, Clone)]
+pub struct ListSummary {
+    pub name: String,
+    pub total: usize,
+    pub completed: usize,
+    // échéance la plus proche parmi les éléments non terminés
+    pub next_due: Option<NaiveDate>,
+}
+
+impl ListSummary {
+    pub fn pending(&self) -> usize {
+        self.total - self.completed
+    }
+
+    pub fn completion_rate(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.completed as f64 * 100.0 / self.total as f64 }
+    }
+}
+
+// totaux de toutes les listes sauvegardées, avec le détail par liste
+#[derive(Debug, Clone)]
+pub struct GlobalStats {
+    pub total: usize,
+    pub completed: usize,
+    pub lists: Vec<ListSummary>,
+}
+
+impl GlobalStats {
+    pub fn from_summaries(lists: Vec<ListSummary>) -> GlobalStats {
+        GlobalStats {
+            total: lists.iter().map(|list| list.total).sum(),
+            completed: lists.iter().map(|list| list.completed).sum(),
+            lists,
+        }
+    }
+
+    // pourcentage d'éléments terminés, 0 sans aucun élément
+    pub fn completion_rate(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.completed as f64 * 100.0 / self.total as f64 }
+    }
+
+    // la liste qui a le plus d'éléments en attente (puis le plus d'éléments)
+    pub fn busiest(&self) -> Option<&ListSummary> {
+        self.lists
+            .iter()
+            .filter(|list| list.total > 0)
+            .max_by(|a, b| a.pending().cmp(&b.pending()).then(a.total.cmp(&b.total)).then(b.name.cmp(&a.name)))
+    }
+
+    // les listes vides ne comptent pas: elles n'ont pas de taux
+    pub fn lowest_completion(&self) -> Option<&ListSummary> {
+        self.lists
+            .iter()
+            .filter(|list| list.total > 0)
+            .min_by(|a, b| a.completion_rate().total_cmp(&b.completion_rate()).then(b.total.cmp(&a.total)).then(a.name.cmp(&b.name)))
+    }
+}
+
+pub fn global_stats() -> GlobalStats {
+    GlobalStats::from_summaries(dashboard_summary())
+}
+
+// une ligne par liste sauvegardée, celles qui ont le plus d'éléments en attente en premier
+pub fn dashboard_summary() -> Vec<ListSummary> {
+    let mut summaries = Vec::new();
+    for list_name in list_saved_todo_lists() {
+        let Ok(todo_list) = load_todo_list(&list_name) else {
+            continue;
+        };
+        let pending = todo_list.items.iter().filter(|item| !item.status.is_done());
+        summaries.push(ListSummary {
+            total: todo_list.items.len(),
+            completed: todo_list.items.iter().filter(|item| item.status.is_done()).count(),
+            next_due: pending.filter_map(|item| item.due_date).min(),
+            name: todo_list.name,
+        });
+    }
+    summaries.sort_by(|a, b| b.pending().cmp(&a.pending()).then_with(|| a.name.cmp(&b.name)));
+    summaries
+}
+
+// rendu en checklist Markdown, prêt à coller dans une issue ou un README
+pub fn export_markdown(list: &TodoList, language: Language, date_format: &str) -> String {
+    let mut markdown = format!("# {}\n\n", list.name);
+    let last_modified = list.last_modified.format(&datetime_format_in(date_format, language)).to_string();
+    markdown.push_str(&format!("{}\n\n", i18n::tr_args_in(language, "export.last_modified", &[&last_modified])));
+    for item in &list.items {
+        let checkbox = if item.status.is_done() { "[x]" } else { "[ ]" };
+        markdown.push_str(&format!("- {} {}\n", checkbox, item.title));
+        if let Some(desc) = &item.description {
+            markdown.push_str(&format!("  - {}\n", desc.replace('\n', "\n    ")));
+        }
+    }
+    markdown
+}
+
+pub const CSV_HEADER: &str = "id,title,description,completed,created_at,completed_at,status,priority,due_date";
+
+// entoure le champ de guillemets s'il contient une virgule, un guillemet ou un retour à la ligne
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn export_csv(list: &TodoList) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for item in &list.items {
+        let fields = [
+            item.id.to_string(),
+            csv_field(&item.title),
+            csv_field(item.description.as_deref().unwrap_or("")),
+            item.status.is_done().to_string(),
+            item.created_at.to_rfc3339(),
+            item.completed_at.map(|date| date.to_rfc3339()).unwrap_or_default(),
+            format!("{:?}", item.status),
+            format!("{:?}", item.priority),
+            item.due_date.map(|date| date.to_string()).unwrap_or_default(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+// texte d'une propriété iCalendar: \ ; , et les retours à la ligne sont échappés
+fn ics_text(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace("\r\n", "\\n").replace('\n', "\\n")
+}
+
+// les lignes de plus de 75 octets continuent sur la suivante, précédée d'une espace (RFC 5545)
+fn ics_line(ics: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            ics.push_str("\r\n ");
+            width = 1;
+        }
+        ics.push(c);
+        width += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+fn ics_timestamp(ts: DateTime<Utc>) -> String {
+    ts.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// un VTODO par élément (DUE n'existe que pour les tâches, pas pour les VEVENT),
+// à importer dans un agenda ou un gestionnaire de tâches compatible
+pub fn export_ics(list: &TodoList) -> String {
+    let mut ics = String::new();
+    ics_line(&mut ics, "BEGIN:VCALENDAR");
+    ics_line(&mut ics, "VERSION:2.0");
+    ics_line(&mut ics, "PRODID:-//ToDoCLI//FR");
+    ics_line(&mut ics, &format!("X-WR-CALNAME:{}", ics_text(&list.name)));
+    for item in &list.items {
+        let (status, priority) = (
+            match item.status {
+                TaskStatus::Afaire | TaskStatus::EnAttente => "NEEDS-ACTION",
+                TaskStatus::EnCours => "IN-PROCESS",
+                TaskStatus::Terminee => "COMPLETED",
+            },
+            match item.priority {
+                Priority::Critical => 1,
+                Priority::High => 3,
+                Priority::Medium => 5,
+                Priority::Low => 9,
+            },
+        );
+        ics_line(&mut ics, "BEGIN:VTODO");
+        ics_line(&mut ics, &format!("UID:{}-{}@todocli", sanitize_name(&list.name), item.id));
+        ics_line(&mut ics, &format!("DTSTAMP:{}", ics_timestamp(list.last_modified)));
+        ics_line(&mut ics, &format!("CREATED:{}", ics_timestamp(item.created_at)));
+        ics_line(&mut ics, &format!("SUMMARY:{}", ics_text(&item.title)));
+        if let Some(desc) = &item.description {
+            ics_line(&mut ics, &format!("DESCRIPTION:{}", ics_text(desc)));
+        }
+        if let Some(due_date) = item.due_date {
+            ics_line(&mut ics, &format!("DUE;VALUE=DATE:{}", due_date.format("%Y%m%d")));
+        }
+        ics_line(&mut ics, &format!("STATUS:{}", status));
+        ics_line(&mut ics, &format!("PRIORITY:{}", priority));
+        if let Some(completed_at) = item.completed_at {
+            ics_line(&mut ics, &format!("COMPLETED:{}", ics_timestamp(completed_at)));
+        }
+        if !item.tags.is_empty() {
+            let tags: Vec<String> = item.tags.iter().map(|tag| ics_text(tag)).collect();
+            ics_line(&mut ics, &format!("CATEGORIES:{}", tags.join(",")));
+        }
+        ics_line(&mut ics, "END:VTODO");
+    }
+    ics_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+// découpe un CSV en lignes de champs, en gérant les guillemets et les retours à la ligne dans les champs
+fn parse_csv(csv: &str) -> Result<Vec<Vec<String>>, Box<dyn std::error::Error>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                '"' => in_quotes = true,
+                ',' => row.push(std::mem::take(&mut field)),
+                '\r' => {}
+                '\n' => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push(std::mem::take(&mut row));
+                }
+                _ => field.push(c),
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err("guillemet non fermé à la fin du fichier".into());
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn parse_csv_date(value: &str) -> Result<Option<DateTime<Utc>>, chrono::ParseError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(DateTime::parse_from_rfc3339(value)?.with_timezone(&Utc)))
+}
+
+// les IDs du fichier sont conservés: deux lignes avec le même ID sont refusées
+pub fn import_csv(name: &str, csv: &str) -> Result<TodoList, Box<dyn std::error::Error>> {
+    let mut todo_list = TodoList::new(name.to_string());
+    let rows = parse_csv(csv)?;
+    let mut seen_ids: BTreeMap<u32, usize> = BTreeMap::new();
+
+    for (index, row) in rows.iter().enumerate() {
+        let line = index + 1;
+        if row.iter().all(|field| field.is_empty()) || (index == 0 && row[0] == "id") {
+            continue;
+        }
+        if row.len() < 6 {
+            return Err(format!("ligne {}: 6 colonnes attendues, {} trouvées", line, row.len()).into());
+        }
+
+        let id: u32 = row[0].parse().map_err(|_| format!("ligne {}: ID invalide '{}'", line, row[0]))?;
+        if let Some(first_line) = seen_ids.insert(id, line) {
+            return Err(format!("ligne {}: l'ID {} est déjà utilisé ligne {}", line, id, first_line).into());
+        }
+        let completed: bool = row[3].parse().map_err(|_| format!("ligne {}: valeur 'completed' invalide '{}'", line, row[3]))?;
+        let created_at = parse_csv_date(&row[4])
+            .map_err(|e| format!("ligne {}: date de création invalide: {}", line, e))?
+            .unwrap_or_else(Utc::now);
+        let completed_at = parse_csv_date(&row[5]).map_err(|e| format!("ligne {}: date de complétion invalide: {}", line, e))?;
+
+        // colonnes optionnelles, absentes des CSV produits par d'autres outils
+        let optional = |column: usize| row.get(column).filter(|value| !value.is_empty());
+        let status = match optional(6) {
+            Some(value) => serde_json::from_value(serde_json::Value::String(value.clone()))
+                .map_err(|_| format!("ligne {}: état invalide '{}'", line, value))?,
+            None if completed => TaskStatus::Terminee,
+            None => TaskStatus::Afaire,
+        };
+        let priority = match optional(7) {
+            Some(value) => serde_json::from_value(serde_json::Value::String(value.clone()))
+                .map_err(|_| format!("ligne {}: priorité invalide '{}'", line, value))?,
+            None => Priority::default(),
+        };
+        let due_date = match optional(8) {
+            Some(value) => Some(value.parse::<NaiveDate>().map_err(|e| format!("ligne {}: échéance invalide: {}", line, e))?),
+            None => None,
+        };
+
+        let description = if row[2].is_empty() { None } else { Some(row[2].clone()) };
+        let mut item = TodoItem::new(id, row[1].clone(), description, priority, due_date);
+        item.status = status;
+        item.created_at = created_at;
+        item.completed_at = completed_at;
+        todo_list.items.push(item);
+    }
+
+    todo_list.backfill_next_id();
+    todo_list.backfill_positions();
+    Ok(todo_list)
+}
+
+// une tâche par ligne non vide; "[x]" en tête marque la tâche comme terminée, "[ ]" est
+// ignoré, tout comme une puce "- " ou "* " devant (une checklist Markdown se réimporte)
+pub fn import_text(name: &str, path: &str) -> Result<TodoList, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let mut todo_list = TodoList::new(name.to_string());
+    for line in content.lines() {
+        let line = line.trim();
+        let line = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")).unwrap_or(line).trim_start();
+        let (done, title) = match line.get(..3) {
+            Some("[x]" | "[X]") => (true, line[3..].trim()),
+            Some("[ ]") => (false, line[3..].trim()),
+            _ => (false, line),
+        };
+        if title.is_empty() {
+            continue;
+        }
+        todo_list.add_item(title.to_string(), None);
+        if done {
+            let id = todo_list.items.last().map(|item| item.id).unwrap_or_default();
+            let _ = todo_list.toggle_item(id, SubtaskRule::Ignore);
+        }
+    }
+    Ok(todo_list)
+}
+
+// "travail, #maison" -> ["travail", "maison"], sans doublons
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',') {
+        let tag = tag.trim().trim_start_matches('#').trim();
+        if !tag.is_empty() && !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // date fixe dans le passé: toute modification doit la faire avancer
+    fn stale(todo_list: &mut TodoList) -> DateTime<Utc> {
+        todo_list.last_modified = Utc::now() - Duration::days(1);
+        todo_list.last_modified
+    }
+
+    #[test]
+    fn add_item_assigns_increasing_ids() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("premier".to_string(), None);
+        todo_list.add_item("second".to_string(), Some("détails".to_string()));
+        assert_eq!(todo_list.items[0].id, 1);
+        assert_eq!(todo_list.items[1].id, 2);
+        assert_eq!(todo_list.items[1].description.as_deref(), Some("détails"));
+
+        // un ID supprimé n'est pas réattribué
+        assert!(todo_list.remove_item(2));
+        todo_list.add_item("troisième".to_string(), None);
+        assert_eq!(todo_list.items[1].id, 3);
+    }
+
+    #[test]
+    fn remove_missing_id_returns_false() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("tâche".to_string(), None);
+        assert!(!todo_list.remove_item(42));
+        assert_eq!(todo_list.items.len(), 1);
+    }
+
+    #[test]
+    fn toggle_item_flips_status_and_completed_at() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("tâche".to_string(), None);
+
+        assert_eq!(todo_list.toggle_item(1, SubtaskRule::Ignore), Ok(()));
+        assert!(todo_list.items[0].status.is_done());
+        assert!(todo_list.items[0].completed_at.is_some());
+
+        assert_eq!(todo_list.toggle_item(1, SubtaskRule::Ignore), Ok(()));
+        assert!(!todo_list.items[0].status.is_done());
+        assert!(todo_list.items[0].completed_at.is_none());
+
+        assert_eq!(todo_list.toggle_item(42, SubtaskRule::Ignore), Err(ToggleError::NotFound));
+    }
+
+    #[test]
+    fn mutations_advance_last_modified() {
+        let mut todo_list = TodoList::new("test".to_string());
+
+        let before = stale(&mut todo_list);
+        todo_list.add_item("tâche".to_string(), None);
+        assert!(todo_list.last_modified > before);
+
+        let before = stale(&mut todo_list);
+        todo_list.toggle_item(1, SubtaskRule::Ignore).expect("élément présent");
+        assert!(todo_list.last_modified > before);
+
+        let before = stale(&mut todo_list);
+        todo_list.remove_item(1);
+        assert!(todo_list.last_modified > before);
+
+        // un ID inconnu ne modifie rien
+        let before = stale(&mut todo_list);
+        todo_list.remove_item(1);
+        assert_eq!(todo_list.toggle_item(1, SubtaskRule::Ignore), Err(ToggleError::NotFound));
+        assert_eq!(todo_list.last_modified, before);
+    }
+
+    #[test]
+    fn cycle_status_sets_completed_at_only_when_done() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("tâche".to_string(), None);
+
+        assert_eq!(todo_list.cycle_status(1, SubtaskRule::Ignore), Ok(TaskStatus::EnCours));
+        assert!(todo_list.items[0].completed_at.is_none());
+        assert_eq!(todo_list.cycle_status(1, SubtaskRule::Ignore), Ok(TaskStatus::Terminee));
+        assert!(todo_list.items[0].completed_at.is_some());
+        assert_eq!(todo_list.cycle_status(1, SubtaskRule::Ignore), Ok(TaskStatus::Afaire));
+        assert!(todo_list.items[0].completed_at.is_none());
+        assert_eq!(todo_list.cycle_status(42, SubtaskRule::Ignore), Err(ToggleError::NotFound));
+    }
+
+    #[test]
+    fn legacy_completed_flag_maps_to_status() {
+        let value = serde_json::json!({
+            "name": "ancienne",
+            "items": [
+                { "id": 1, "title": "faite", "description": null, "completed": true,
+                  "created_at": "2024-01-01T00:00:00Z", "completed_at": "2024-01-02T00:00:00Z" },
+                { "id": 2, "title": "à faire", "description": null, "completed": false,
+                  "created_at": "2024-01-01T00:00:00Z", "completed_at": null }
+            ],
+            "created_at": "2024-01-01T00:00:00Z",
+            "last_modified": "2024-01-01T00:00:00Z"
+        });
+        let todo_list = migrate(value).expect("liste valide après migration");
+        assert_eq!(todo_list.items[0].status, TaskStatus::Terminee);
+        assert_eq!(todo_list.items[1].status, TaskStatus::Afaire);
+        assert_eq!(todo_list.schema_version, SCHEMA_VERSION);
+        assert_eq!(todo_list.next_id, 3);
+        assert_eq!(todo_list.items[1].position, 2);
+    }
+
+    #[test]
+    fn move_item_renumbers_positions_and_enables_manual_order() {
+        let mut todo_list = TodoList::new("test".to_string());
+        for title in ["a", "b", "c"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+        assert_eq!(todo_list.default_sort(), SortKey::Priority);
+
+        assert!(todo_list.move_item(3, 1));
+        let order: Vec<(u32, u32)> = todo_list.items.iter().map(|item| (item.id, item.position)).collect();
+        assert_eq!(order, vec![(3, 1), (1, 2), (2, 3)]);
+        assert_eq!(todo_list.default_sort(), SortKey::Manual);
+    }
+
+    #[test]
+    fn toggle_by_title_distinguishes_missing_and_ambiguous() {
+        let mut todo_list = TodoList::new("test".to_string());
+        for title in ["Courses", "courses", "Ménage"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+
+        assert_eq!(todo_list.toggle_by_title("ménage", SubtaskRule::Ignore), Ok(()));
+        assert!(todo_list.items[2].status.is_done());
+        assert_eq!(todo_list.toggle_by_title("COURSES", SubtaskRule::Ignore), Err(ToggleError::Ambiguous(vec![1, 2])));
+        assert_eq!(todo_list.toggle_by_title("jardin", SubtaskRule::Ignore), Err(ToggleError::NotFound));
+    }
+
+    #[test]
+    fn history_records_title_even_after_removal() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("tâche".to_string(), None);
+        todo_list.toggle_item(1, SubtaskRule::Ignore).expect("élément présent");
+        todo_list.remove_item(1);
+
+        let actions: Vec<HistoryAction> = todo_list.history.iter().map(|entry| entry.action).collect();
+        assert_eq!(actions, vec![HistoryAction::Add, HistoryAction::Complete, HistoryAction::Remove]);
+        assert!(todo_list.history.iter().all(|entry| entry.item_id == 1 && entry.title == "tâche"));
+    }
+
+    #[test]
+    fn wrap_text_breaks_between_words_and_splits_long_words() {
+        assert_eq!(wrap_text("un deux trois", 7), vec!["un deux", "trois"]);
+        assert_eq!(wrap_text("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap_text("", 10), vec![""]);
+    }
+
+    #[test]
+    fn completion_histogram_groups_by_week_and_ignores_pending_items() {
+        let mut todo_list = TodoList::new("test".to_string());
+        for title in ["lundi", "mercredi", "en cours"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+        for (id, day) in [(1, "2024-01-01"), (2, "2024-01-03")] {
+            todo_list.toggle_item(id, SubtaskRule::Ignore).expect("élément présent");
+            let completed_at = format!("{}T12:00:00Z", day).parse().expect("date valide");
+            todo_list.items[id as usize - 1].completed_at = Some(completed_at);
+        }
+
+        assert_eq!(completion_histogram(&todo_list, Bucket::Day, Language::Fr, "%d/%m/%Y").len(), 2);
+        let weekly = completion_histogram(&todo_list, Bucket::Week, Language::Fr, "%d/%m/%Y");
+        assert_eq!(weekly, vec![("semaine du 01/01/2024".to_string(), 2)]);
+        let weekly = completion_histogram(&todo_list, Bucket::Week, Language::En, "%m/%d/%Y");
+        assert_eq!(weekly[0].0, "week of 01/01/2024");
+    }
+
+    #[test]
+    fn next_task_prefers_priority_then_due_date_and_skips_waiting_items() {
+        let mut todo_list = TodoList::new("test".to_string());
+        let soon = Local::now().date_naive() + Duration::days(1);
+        let later = soon + Duration::days(7);
+        todo_list.add_item_with_details("bloquée".to_string(), None, Priority::Critical, Some(soon));
+        todo_list.add_item_with_details("plus tard".to_string(), None, Priority::High, Some(later));
+        todo_list.add_item_with_details("bientôt".to_string(), None, Priority::High, Some(soon));
+        todo_list.add_item_with_details("faite".to_string(), None, Priority::Critical, None);
+        todo_list.update_item_status(1, TaskStatus::EnAttente, SubtaskRule::Ignore).expect("élément présent");
+        todo_list.toggle_item(4, SubtaskRule::Ignore).expect("élément présent");
+
+        assert_eq!(next_task(&todo_list).map(|item| item.id), Some(3));
+
+        for id in 1..=3 {
+            todo_list.update_item_status(id, TaskStatus::Terminee, SubtaskRule::Ignore).expect("élément présent");
+        }
+        assert!(next_task(&todo_list).is_none());
+    }
+
+    #[test]
+    fn import_csv_keeps_ids_and_rejects_duplicates() {
+        let header = "id,title,description,completed,created_at,completed_at\n";
+        let todo_list = import_csv("test", &format!("{}3,pain,,false,,\n7,lait,,true,,\n", header)).expect("CSV valide");
+        let ids: Vec<u32> = todo_list.items.iter().map(|item| item.id).collect();
+        assert_eq!(ids, vec![3, 7]);
+        assert_eq!(todo_list.next_id, 8);
+
+        let error = import_csv("test", &format!("{}3,pain,,false,,\n3,lait,,false,,\n", header)).unwrap_err();
+        assert_eq!(error.to_string(), "ligne 3: l'ID 3 est déjà utilisé ligne 2");
+    }
+
+    #[test]
+    fn exhausted_id_counter_compacts_ids_instead_of_reusing_them() {
+        let mut todo_list = TodoList::new("test".to_string());
+        for i in 0..1000 {
+            todo_list.add_item(format!("tâche {}", i), None);
+        }
+        todo_list.remove_item(500);
+        todo_list.next_id = u32::MAX - 2;
+        for i in 0..5 {
+            todo_list.add_item(format!("nouvelle {}", i), None);
+        }
+
+        let ids: Vec<u32> = todo_list.items.iter().map(|item| item.id).collect();
+        let expected: Vec<u32> = (1..=1004).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(todo_list.next_id, 1005);
+        // la renumérotation (999 éléments + "nouvelle 0" déjà ajoutée) est signalée une seule fois
+        assert_eq!(todo_list.take_compaction(), Some(1001));
+        assert_eq!(todo_list.take_compaction(), None);
+    }
+
+    #[test]
+    fn renumber_follows_display_order() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item_with_details("basse".to_string(), None, Priority::Low, None);
+        todo_list.add_item_with_details("supprimée".to_string(), None, Priority::Low, None);
+        todo_list.add_item_with_details("critique".to_string(), None, Priority::Critical, None);
+        todo_list.remove_item(2);
+        todo_list.renumber();
+
+        let titles: Vec<(u32, &str)> = todo_list.items.iter().map(|item| (item.id, item.title.as_str())).collect();
+        assert_eq!(titles, vec![(2, "basse"), (1, "critique")]);
+        assert_eq!(todo_list.next_id, 3);
+    }
+
+    #[test]
+    fn completed_between_includes_boundaries() {
+        let mut todo_list = TodoList::new("test".to_string());
+        for title in ["début", "fin", "après", "en cours"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+        let start: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().expect("date valide");
+        let end: DateTime<Utc> = "2024-01-07T23:59:59Z".parse().expect("date valide");
+        for (index, completed_at) in [start, end, end + Duration::seconds(1)].into_iter().enumerate() {
+            todo_list.toggle_item(index as u32 + 1, SubtaskRule::Ignore).expect("élément présent");
+            todo_list.items[index].completed_at = Some(completed_at);
+        }
+
+        let titles: Vec<&str> = todo_list.completed_between(start, end).iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(titles, vec!["début", "fin"]);
+    }
+
+    #[test]
+    fn items_by_category_groups_case_insensitively() {
+        let mut todo_list = TodoList::new("test".to_string());
+        for title in ["courses", "ménage", "rapport", "libre"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+        todo_list.set_category(1, Some("Maison".to_string()), Some(LabelColor::Green));
+        todo_list.set_category(2, Some("maison".to_string()), None);
+        todo_list.set_category(3, Some("Bureau".to_string()), Some(LabelColor::Blue));
+
+        let groups: Vec<(Option<String>, usize)> =
+            todo_list.items_by_category().into_iter().map(|(category, items)| (category, items.len())).collect();
+        assert_eq!(groups, vec![(Some("Bureau".to_string()), 1), (Some("Maison".to_string()), 2), (None, 1)]);
+
+        // retirer la catégorie efface aussi la couleur
+        todo_list.set_category(1, None, Some(LabelColor::Red));
+        assert_eq!(todo_list.items[0].category_color, None);
+    }
+
+    #[test]
+    fn items_by_priority_puts_pending_items_first() {
+        let mut todo_list = TodoList::new("test".to_string());
+        for title in ["a", "b", "c", "d"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+        todo_list.update_item_priority(1, Priority::High);
+        todo_list.update_item_priority(2, Priority::High);
+        todo_list.update_item_priority(4, Priority::Low);
+        todo_list.toggle_item(1, SubtaskRule::Ignore).expect("élément présent");
+
+        let groups: Vec<(Priority, Vec<u32>)> = todo_list
+            .items_by_priority()
+            .into_iter()
+            .map(|(priority, items)| (priority, items.iter().map(|item| item.id).collect()))
+            .collect();
+        assert_eq!(groups, vec![(Priority::High, vec![2, 1]), (Priority::Medium, vec![3]), (Priority::Low, vec![4])]);
+    }
+
+    #[test]
+    fn oldest_incomplete_skips_completed_items() {
+        let mut todo_list = TodoList::new("test".to_string());
+        assert!(todo_list.oldest_incomplete().is_none());
+        for title in ["ancienne", "moyenne", "récente"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+        let now = Utc::now();
+        for (item, days) in todo_list.items.iter_mut().zip([30, 10, 1]) {
+            item.created_at = now - Duration::days(days);
+        }
+        assert_eq!(todo_list.oldest_incomplete().map(|item| item.id), Some(1));
+
+        todo_list.toggle_item(1, SubtaskRule::Ignore).expect("élément présent");
+        assert_eq!(todo_list.oldest_incomplete().map(|item| item.id), Some(2));
+        todo_list.toggle_item(2, SubtaskRule::Ignore).expect("élément présent");
+        todo_list.toggle_item(3, SubtaskRule::Ignore).expect("élément présent");
+        assert!(todo_list.oldest_incomplete().is_none());
+    }
+
+    #[test]
+    fn export_ics_escapes_and_folds_lines() {
+        let mut todo_list = TodoList::new("Mes courses".to_string());
+        todo_list.add_item_with_details(
+            "lait, œufs; pain".to_string(),
+            Some(format!("ligne 1\n{}", "x".repeat(80))),
+            Priority::High,
+            NaiveDate::from_ymd_opt(2026, 3, 9),
+        );
+        todo_list.add_item("sans date".to_string(), None);
+        todo_list.toggle_item(2, SubtaskRule::Ignore).expect("élément présent");
+
+        let ics = export_ics(&todo_list);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n") && ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VTODO").count(), 2);
+        assert!(ics.contains("UID:mes_courses-1@todocli\r\n"));
+        assert!(ics.contains("SUMMARY:lait\\, œufs\\; pain\r\n"));
+        assert!(ics.contains("DUE;VALUE=DATE:20260309\r\n"));
+        assert!(ics.contains("PRIORITY:3\r\n"));
+        assert!(ics.contains("STATUS:COMPLETED\r\n"));
+        assert_eq!(ics.matches("DUE").count(), 1);
+        // aucune ligne ne dépasse 75 octets, la description longue continue après "\r\n "
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        assert!(ics.contains("DESCRIPTION:ligne 1\\n"));
+        assert!(ics.contains("\r\n x"));
+    }
+
+    #[test]
+    fn unsaved_changes_counts_each_modification() {
+        let mut todo_list = TodoList::new("test".to_string());
+        assert!(!todo_list.is_dirty());
+        todo_list.add_item("a".to_string(), None);
+        todo_list.add_items_bulk(["b", "c"]);
+        todo_list.update_view(|prefs| prefs.grouped = true);
+        assert_eq!(todo_list.unsaved_changes(), 3);
+        // rien à rétablir deux fois de suite: seul le premier appel compte
+        todo_list.reset_view();
+        todo_list.reset_view();
+        assert_eq!(todo_list.unsaved_changes(), 4);
+    }
+
+    #[test]
+    fn postpone_overdue_only_moves_late_pending_items() {
+        let today = Local::now().date_naive();
+        let mut todo_list = TodoList::new("test".to_string());
+        for (title, due_date) in [
+            ("en retard", Some(today - Duration::days(10))),
+            ("terminée en retard", Some(today - Duration::days(10))),
+            ("à venir", Some(today + Duration::days(2))),
+            ("sans date", None),
+        ] {
+            todo_list.add_item_with_details(title.to_string(), None, Priority::Medium, due_date);
+        }
+        todo_list.toggle_item(2, SubtaskRule::Ignore).expect("élément présent");
+
+        assert_eq!(todo_list.postpone_overdue(Duration::weeks(1)), 1);
+        let due_dates: Vec<Option<NaiveDate>> = todo_list.items.iter().map(|item| item.due_date).collect();
+        assert_eq!(
+            due_dates,
+            vec![Some(today - Duration::days(3)), Some(today - Duration::days(10)), Some(today + Duration::days(2)), None]
+        );
+    }
+
+    #[test]
+    fn review_report_groups_overdue_due_today_and_completed() {
+        let now = Utc::now();
+        let today = now.with_timezone(&Local).date_naive();
+        let mut todo_list = TodoList::new("travail".to_string());
+        for title in ["rapport", "appel", "facture", "réunion", "plus tard"] {
+            todo_list.add_item(title.to_string(), None);
+        }
+        todo_list.update_item_due_date(1, Some(today - Duration::days(3)));
+        todo_list.update_item_due_date(2, Some(today));
+        todo_list.update_item_priority(2, Priority::High);
+        todo_list.update_item_due_date(3, Some(today));
+        todo_list.toggle_item(3, SubtaskRule::Ignore).expect("élément présent");
+        todo_list.update_item_due_date(4, Some(today - Duration::days(1)));
+        todo_list.toggle_item(4, SubtaskRule::Ignore).expect("élément présent");
+        todo_list.items[3].completed_at = Some(now - Duration::days(2));
+        todo_list.update_item_due_date(5, Some(today + Duration::days(1)));
+
+        let report = review_report(&[todo_list], now, Language::Fr, "%d/%m/%Y");
+        let sections: Vec<&str> = report.split("\n\n").collect();
+        assert_eq!(sections.len(), 4);
+        assert!(sections[1].starts_with("En retard (1):") && sections[1].contains("[travail] 1 - rapport (échéance"));
+        assert!(sections[1].contains("3 jour(s) de retard"));
+        assert!(sections[2].starts_with("À faire aujourd'hui (1):") && sections[2].contains("[travail] 2 - appel"));
+        assert_eq!(sections[3], "Terminé aujourd'hui (1):\n  [travail] 3 - facture\n");
+        assert!(!report.contains("plus tard") && !report.contains("réunion"));
+
+        let empty = review_report(&[], now, Language::Fr, "%d/%m/%Y");
+        assert_eq!(empty.matches("  rien").count(), 3);
+        let english = review_report(&[], now, Language::En, "%m/%d/%Y");
+        assert!(english.starts_with(&format!("Review for {}\n", today.format("%m/%d/%Y"))));
+        assert_eq!(english.matches("  nothing").count(), 3);
+    }
+
+    #[test]
+    fn completing_a_parent_requires_its_subtasks() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("déménager".to_string(), None);
+        todo_list.add_subtask(1, "cartons".to_string());
+        todo_list.add_subtask(1, "camion".to_string());
+        todo_list.toggle_subtask(1, 1);
+        let pending = ToggleError::PendingSubtasks(vec!["camion".to_string()]);
+
+        // chaque façon de terminer l'élément applique la règle
+        assert_eq!(todo_list.toggle_item(1, SubtaskRule::Enforce), Err(pending.clone()));
+        assert_eq!(todo_list.update_item_status(1, TaskStatus::Terminee, SubtaskRule::Enforce), Err(pending.clone()));
+        assert_eq!(todo_list.complete_with_follow_up(1, "suite".to_string(), None, SubtaskRule::Enforce), Err(pending.clone()));
+        assert_eq!(todo_list.toggle_range(&[1], SubtaskRule::Enforce), (0, vec![1]));
+        assert_eq!(todo_list.cycle_status(1, SubtaskRule::Enforce), Ok(TaskStatus::EnCours));
+        assert_eq!(todo_list.cycle_status(1, SubtaskRule::Enforce), Err(pending));
+        assert_eq!(todo_list.items.len(), 1);
+        assert!(!todo_list.items[0].status.is_done());
+
+        assert_eq!(todo_list.toggle_item(1, SubtaskRule::Ignore), Ok(()));
+        assert!(todo_list.items[0].status.is_done());
+        // rouvrir n'est jamais bloqué
+        assert_eq!(todo_list.toggle_item(1, SubtaskRule::Enforce), Ok(()));
+        assert_eq!(todo_list.toggle_item(42, SubtaskRule::Enforce), Err(ToggleError::NotFound));
+
+        // cocher la dernière sous-tâche termine le parent
+        todo_list.toggle_subtask(1, 2);
+        assert!(todo_list.items[0].status.is_done());
+    }
+
+    #[test]
+    fn view_prefs_persist_and_drive_default_sort() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("a".to_string(), None);
+        todo_list.add_item("b".to_string(), None);
+        todo_list.update_view(|prefs| prefs.sort = Some(SortKey::DueDate));
+        todo_list.update_view(|prefs| prefs.grouped = true);
+
+        let json = serde_json::to_string(&todo_list).expect("sérialisation");
+        let reloaded: TodoList = serde_json::from_str(&json).expect("désérialisation");
+        assert_eq!(
+            reloaded.view_prefs,
+            Some(ViewPrefs { sort: Some(SortKey::DueDate), filter: None, grouped: true })
+        );
+        assert_eq!(reloaded.default_sort(), SortKey::DueDate);
+        let prefs = reloaded.view_prefs.expect("vue enregistrée");
+        assert_eq!(prefs.describe_in(Language::Fr), "tri: Date d'échéance, par catégorie");
+        assert_eq!(prefs.describe_in(Language::En), "sort: Due date, by category");
+
+        // déplacer un élément remplace le tri enregistré par l'ordre manuel
+        todo_list.move_item(2, 1);
+        assert_eq!(todo_list.default_sort(), SortKey::Manual);
+        todo_list.update_view(|prefs| prefs.grouped = false);
+        assert_eq!(todo_list.view_prefs, None);
+    }
+
+    #[test]
+    fn global_stats_picks_busiest_and_lowest_completion() {
+        let summary = |name: &str, total, completed| ListSummary { name: name.to_string(), total, completed, next_due: None };
+        let stats = GlobalStats::from_summaries(vec![
+            summary("courses", 10, 8),
+            summary("travail", 6, 1),
+            summary("vide", 0, 0),
+        ]);
+        assert_eq!((stats.total, stats.completed), (16, 9));
+        assert!((stats.completion_rate() - 56.25).abs() < f64::EPSILON);
+        assert_eq!(stats.busiest().map(|list| list.name.as_str()), Some("travail"));
+        assert_eq!(stats.lowest_completion().map(|list| list.name.as_str()), Some("travail"));
+
+        let empty = GlobalStats::from_summaries(Vec::new());
+        assert_eq!(empty.completion_rate(), 0.0);
+        assert!(empty.busiest().is_none());
+    }
+
+    #[test]
+    fn display_formats_into_a_string() {
+        let mut todo_list = TodoList::new("courses".to_string());
+        todo_list.add_item("pain".to_string(), Some("complet".to_string()));
+        todo_list.add_link(1, "https://boulangerie.fr");
+
+        let options = DisplayOptions::default();
+        let item = todo_list.items[0].render(&options);
+        assert!(item.starts_with("□ [1] [MOYENNE] pain - À faire"));
+        assert!(item.contains("    Description: complet\n"));
+        assert!(item.contains("    → https://boulangerie.fr\n"));
+        assert_eq!(todo_list.items[0].to_string(), item);
+
+        let list = todo_list.render(&options);
+        assert!(list.contains("=== courses ==="));
+        assert!(list.ends_with(&item));
+        assert_eq!(todo_list.to_string(), list);
+
+        let english = DisplayOptions { language: Language::En, title_width: 3, ..DisplayOptions::default() };
+        assert!(todo_list.items[0].render(&english).starts_with("□ [1] [MEDIUM] pa… - To do"));
+        assert!(todo_list.items[0].detail_block(&english).starts_with("□ [1] [MEDIUM] pain - To do"));
+
+        todo_list.update_view(|prefs| prefs.grouped = true);
+        assert!(todo_list.render(&options).contains("▸ Sans catégorie (1)"));
+        assert!(todo_list.render(&english).contains("▸ Uncategorized (1)"));
+
+        todo_list.toggle_item(1, SubtaskRule::Ignore).expect("élément présent");
+        let hidden = DisplayOptions { show_completed_at: false, ..DisplayOptions::default() };
+        assert!(todo_list.items[0].render(&options).contains("✅ Terminé le: "));
+        assert!(!todo_list.items[0].render(&hidden).contains("✅"));
+    }
+
+    #[test]
+    fn parse_due_accepts_shortcuts_and_dates() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 31).expect("date valide");
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        assert_eq!(parse_due_from("Aujourd’hui", today, "%d/%m/%Y"), Some(today));
+        assert_eq!(parse_due_from("demain", today, "%d/%m/%Y"), date(2025, 2, 1));
+        assert_eq!(parse_due_from("après-demain", today, "%d/%m/%Y"), date(2025, 2, 2));
+        assert_eq!(parse_due_from("+3j", today, "%d/%m/%Y"), date(2025, 2, 3));
+        assert_eq!(parse_due_from("+1s", today, "%d/%m/%Y"), date(2025, 2, 7));
+        // fin de mois: on retombe sur le dernier jour du mois suivant
+        assert_eq!(parse_due_from("+1m", today, "%d/%m/%Y"), date(2025, 2, 28));
+        assert_eq!(parse_due_from("25/12/2025", today, "%d/%m/%Y"), date(2025, 12, 25));
+        assert_eq!(parse_due_from("12/25/2025", today, "%m/%d/%Y"), date(2025, 12, 25));
+        assert_eq!(parse_due_from("25/12/2025", today, "%m/%d/%Y"), None);
+        for invalid in ["", "bientôt", "+j", "+3x", "3j", "32/01/2025"] {
+            assert_eq!(parse_due_from(invalid, today, "%d/%m/%Y"), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn follow_up_survives_renumbering() {
+        let mut todo_list = TodoList::new("test".to_string());
+        todo_list.add_item("supprimée".to_string(), None);
+        todo_list.add_item("rédiger".to_string(), None);
+        todo_list.remove_item(1);
+        let follow_up = todo_list.complete_with_follow_up(2, "relire".to_string(), None, SubtaskRule::Ignore);
+        assert_eq!(follow_up, Ok(3));
+        assert!(todo_list.items[0].status.is_done());
+        assert_eq!(todo_list.complete_with_follow_up(9, "rien".to_string(), None, SubtaskRule::Ignore), Err(ToggleError::NotFound));
+
+        // l'élément non terminé passe devant: "relire" devient 1, "rédiger" 2
+        todo_list.update_view(|prefs| prefs.sort = Some(SortKey::Status));
+        todo_list.renumber();
+        let relire = todo_list.items.iter().find(|item| item.title == "relire").expect("suite présente");
+        assert_eq!((relire.id, relire.follows), (1, Some(2)));
+    }
+}
@@ -1,424 +1,588 @@
-use std::io;
+use std::env;
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Read};
 use std::fs;
-use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc, NaiveDate, Local};
+use std::process::{self, Command, Stdio};
+use std::time::SystemTime;
+use serde::Serialize;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Local, Utc};
+use todocli::*;
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
-enum Priority {
-    Low,
-    Medium,
-    High,
-    Critical,
+// sauvegarde puis confirme à l'utilisateur, la bibliothèque n'affichant rien
+fn save_list(todo_list: &mut TodoList, verbosity: Verbosity) -> Result<(), Box<dyn std::error::Error>> {
+    report_compaction(todo_list, verbosity);
+    let path = save_todo_list(todo_list)?;
+    if verbosity.shows_success() {
+        println!("Liste '{}' sauvegardée avec succès dans {}!", todo_list.name, path.display());
+    }
+    if verbosity.shows_details() {
+        println!(
+            "{} élément(s), {} archivé(s); historique: {}",
+            todo_list.items.len(),
+            todo_list.archived.len(),
+            history_path(&todo_list.name).display()
+        );
+    }
+    Ok(())
 }
 
-impl Priority {
-    fn to_string(&self) -> &'static str {
-        match self {
-            Priority::Low => "Basse",
-            Priority::Medium => "Moyenne",
-            Priority::High => "Haute",
-            Priority::Critical => "Critique",
-        }
+// le compteur d'ID épuisé a fait renuméroter la liste: les anciens IDs ne sont plus valables
+fn report_compaction(todo_list: &mut TodoList, verbosity: Verbosity) {
+    let compacted = todo_list.take_compaction();
+    print_compaction(compacted, &todo_list.name, verbosity);
+}
+
+fn print_compaction(compacted: Option<usize>, list_name: &str, verbosity: Verbosity) {
+    if let Some(count) = compacted
+        && verbosity.shows_success()
+    {
+        println!("Compteur d'ID épuisé: les {} éléments de '{}' ont été renumérotés à partir de 1.", count, list_name);
     }
+}
 
-    fn to_symbol(&self) -> &'static str {
-        match self {
-            Priority::Low => "🟢",
-            Priority::Medium => "🟡",
-            Priority::High => "🟠",
-            Priority::Critical => "🔴",
+// charge une liste pour le menu interactif; si le fichier est corrompu,
+// indique lequel et propose de le mettre de côté
+fn open_todo_list(name: &str) -> io::Result<Option<TodoList>> {
+    let path = storage_path(name);
+    match load_todo_list(name) {
+        Ok(todo_list) => Ok(Some(todo_list)),
+        Err(e) if e.downcast_ref::<serde_json::Error>().is_some() => {
+            println!("Le fichier '{}' est invalide ou corrompu: {}", path.display(), e);
+            // todos.json contient aussi les autres listes: on ne le déplace pas d'office
+            if config().storage == StorageBackend::Combined {
+                println!("Corrigez-le avec 'Éditer le JSON d'une liste' ou à la main.");
+            } else if confirm("Voulez-vous le renommer en sauvegarde (.bak) pour le mettre de côté?") {
+                let backup_path = path.with_extension(format!("json.{}.bak", Utc::now().format("%Y%m%d%H%M%S")));
+                match fs::rename(&path, &backup_path) {
+                    Ok(()) => println!("Fichier déplacé vers '{}'.", backup_path.display()),
+                    Err(e) => println!("Erreur lors du renommage: {}", e),
+                }
+            } else {
+                println!("Fichier ignoré.");
+            }
+            Ok(None)
+        }
+        Err(e) => {
+            println!("Erreur lors du chargement de '{}': {}", path.display(), e);
+            Ok(None)
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
-enum TaskStatus {
-    Afaire,
-    EnCours,
-    EnAttente,
-    Terminee,
+// lit une ligne au clavier, sans les espaces autour; la fin de l'entrée (Ctrl-D)
+// devient une erreur UnexpectedEof pour que l'appelant puisse quitter proprement
+fn read_line() -> io::Result<String> {
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "fin de l'entrée"));
+    }
+    Ok(input.trim().to_string())
 }
 
-impl TaskStatus {
-    fn to_string(&self) -> &'static str {
-        match self {
-            TaskStatus::Afaire => "À faire",
-            TaskStatus::EnCours => "En cours",
-            TaskStatus::EnAttente => "En attente",
-            TaskStatus::Terminee => "Terminée",
+// titres collés un par ligne, jusqu'à une ligne vide
+fn read_pasted_lines() -> io::Result<Vec<String>> {
+    println!("Collez les titres, un par ligne, puis une ligne vide pour terminer:");
+    let mut lines = Vec::new();
+    loop {
+        match read_line() {
+            Ok(line) if line.is_empty() => return Ok(lines),
+            Ok(line) => lines.push(line),
+            // fin de l'entrée: on garde ce qui a déjà été collé
+            Err(e) if is_end_of_input(&e) && !lines.is_empty() => return Ok(lines),
+            Err(e) => return Err(e),
         }
     }
-    fn to_symbol(&self) -> &'static str {
-        match self {
-            TaskStatus::Afaire => "⬜",
-            TaskStatus::EnCours => "🟦",
-            TaskStatus::EnAttente => "🟨",
-            TaskStatus::Terminee => "✅",
-        }
+}
+
+// "+" ouvre une saisie sur plusieurs lignes, terminée par une ligne "." ou "END"
+fn read_description() -> io::Result<String> {
+    let first = read_line()?;
+    if first != "+" {
+        return Ok(first);
     }
-    fn is_done(&self) -> bool {
-        matches!(self, TaskStatus::Terminee)
+
+    println!("Saisissez la description, puis une ligne contenant seulement '.' ou 'END' pour terminer:");
+    let mut lines = Vec::new();
+    loop {
+        let line = read_line()?;
+        if line == "." || line == "END" {
+            break;
+        }
+        lines.push(line);
     }
+    Ok(lines.join("\n").trim().to_string())
+}
+
+// oui/o/yes/y, sans tenir compte de la casse
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "oui" | "o" | "yes" | "y")
+}
+
+// demande une confirmation; toute autre réponse, une erreur de lecture ou la fin de l'entrée valent non
+fn confirm(prompt: &str) -> bool {
+    println!("{} {}", prompt, tr("common.yes_no"));
+    read_line().is_ok_and(|answer| is_yes(&answer))
+}
+
+fn is_end_of_input(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::UnexpectedEof
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct TodoItem {
-    id: u32,
-    title: String,
-    description: Option<String>,
-    status: TaskStatus,
-    priority: Priority,
-    due_date: Option<NaiveDate>,
-    created_at: DateTime<Utc>,
-    completed_at: Option<DateTime<Utc>>,
+// lit un nombre au clavier, renvoie None si la saisie n'est pas un nombre valide
+fn read_menu_choice() -> io::Result<Option<u32>> {
+    let input = read_line()?;
+    Ok(input.parse().ok())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct TodoList {
-    name: String,
-    items: Vec<TodoItem>,
-    created_at: DateTime<Utc>,
-    last_modified: DateTime<Utc>,
+// mode non canonique du terminal (lecture touche par touche) via stty, rétabli à la fin
+struct RawMode {
+    saved: String,
 }
 
-impl TodoItem {
-    fn new(id: u32, title: String, description: Option<String>, priority: Priority, due_date: Option<NaiveDate>) -> Self {
-        TodoItem {
-            id,
-            title,
-            description,
-            status: TaskStatus::Afaire,
-            priority,
-            due_date,
-            created_at: Utc::now(),
-            completed_at: None,
+impl RawMode {
+    // None si stty n'est pas disponible, par exemple hors d'un terminal Unix
+    fn enable() -> Option<RawMode> {
+        let output = Command::new("stty").arg("-g").stdin(Stdio::inherit()).output().ok()?;
+        if !output.status.success() {
+            return None;
         }
+        let saved = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        let status = Command::new("stty").args(["-icanon", "min", "1"]).stdin(Stdio::inherit()).status().ok()?;
+        status.success().then_some(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = Command::new("stty").arg(&self.saved).stdin(Stdio::inherit()).status();
     }
+}
 
-    fn mark_completed(&mut self) {
-        self.status = TaskStatus::Terminee;
-        self.completed_at = Some(Utc::now());
+fn read_key() -> io::Result<u8> {
+    let mut byte = [0u8];
+    if io::stdin().read(&mut byte)? == 0 || byte[0] == 4 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "fin de l'entrée"));
     }
+    Ok(byte[0])
+}
 
-    fn mark_incomplete(&mut self) {
-        self.status = TaskStatus::Afaire;
-        self.completed_at = None;
+// choix dans un menu de `option_count` entrées: avec single_key_menu (et un terminal),
+// un chiffre suffit; s'il peut commencer un nombre à deux chiffres ("1" pour 10 à 15),
+// on attend le second chiffre ou Entrée
+fn read_menu_selection(option_count: u32) -> io::Result<Option<u32>> {
+    if !config().single_key_menu || !io::stdin().is_terminal() {
+        return read_menu_choice();
     }
+    let Some(_raw_mode) = RawMode::enable() else {
+        return read_menu_choice();
+    };
 
-    fn is_overdue(&self) -> bool {
-        if let Some(due_date) = self.due_date {
-            if self.status.is_done() {
-                return false;
-            }
-            let today = Local::now().date_naive();
-            due_date < today
-        } else {
-            false
-        }
+    let Some(first) = char::from(read_key()?).to_digit(10) else {
+        println!();
+        return Ok(None);
+    };
+    if first * 10 > option_count {
+        println!();
+        return Ok(Some(first));
+    }
+    let second = read_key()?;
+    println!();
+    match char::from(second) {
+        '\n' | '\r' => Ok(Some(first)),
+        c => Ok(c.to_digit(10).map(|digit| first * 10 + digit)),
     }
+}
 
-    fn days_until_due(&self) -> Option<i64> {
-        if let Some(due_date) = self.due_date {
-            let today = Local::now().date_naive();
-            Some((due_date - today).num_days())
-        } else {
-            None
+// ID saisi directement ou retrouvé par le titre, None si aucun élément ne correspond
+fn read_item_id(todo_list: &TodoList) -> io::Result<Option<u32>> {
+    let input = read_line()?;
+    if let Ok(id) = input.parse() {
+        return Ok(Some(id));
+    }
+    match todo_list.resolve_title(&input) {
+        Ok(id) => Ok(Some(id)),
+        Err(LookupError::NotFound) => Ok(None),
+        Err(LookupError::Ambiguous(ids)) => {
+            println!("Plusieurs éléments portent ce titre:");
+            for item in todo_list.items.iter().filter(|item| ids.contains(&item.id)) {
+                println!("[{}] {} - {}", item.id, item.title, item.status.to_string());
+            }
+            println!("Entrez l'ID de l'élément voulu:");
+            let id = read_number()?;
+            Ok(ids.contains(&id).then_some(id))
         }
     }
 }
 
-impl TodoList {
-    fn new(name: String) -> Self {
-        TodoList {
-            name,
-            items: Vec::new(),
-            created_at: Utc::now(),
-            last_modified: Utc::now(),
+// redemande un nombre tant que la saisie n'est pas valide
+fn read_number() -> io::Result<u32> {
+    loop {
+        match read_menu_choice()? {
+            Some(number) => return Ok(number),
+            None => println!("{}", tr("common.invalid_number")),
         }
     }
+}
 
-    fn next_id(&self) -> u32 {
-        self.items
-            .iter()
-            .map(|item| item.id)
-            .max()
-            .unwrap_or(0)
-            .saturating_add(1)
+fn display_due_soon_banner() {
+    let window = config().due_soon_window();
+    let results = due_soon(window);
+    if results.is_empty() {
+        return;
     }
-
-    fn add_item(&mut self, title: String, description: Option<String>) {
-        let id = self.next_id();
-        let item = TodoItem::new(id, title, description, Priority::Low, None);
-        self.items.push(item);
-        self.last_modified = Utc::now();
+    println!("⏰ {} tâche(s) arrivent à échéance dans les {}h:", results.len(), window.num_hours());
+    for (list_name, item) in &results {
+        let due_date = item.due_date.map(format_date).unwrap_or_default();
+        println!("   {} → [{}] {} ({})", list_name, item.id, item.title, due_date);
     }
+    println!();
+}
 
-    fn add_item_with_details(&mut self, title: String, description: Option<String>, priority: Priority, due_date: Option<NaiveDate>) {
-        let id = self.next_id();
-        let item = TodoItem::new(id, title, description, priority, due_date);
-        self.items.push(item);
-        self.last_modified = Utc::now();
+fn get_priority_from_user() -> io::Result<Priority> {
+    println!("{}", tr_args("priority.prompt", &[&Priority::default().to_string()]));
+    for (i, priority) in [Priority::Low, Priority::Medium, Priority::High, Priority::Critical].iter().enumerate() {
+        println!("{} - {} {}", i + 1, priority.to_string(), priority.to_symbol());
+    }
+    
+    loop {
+        let input = read_line()?;
+        if input.is_empty() {
+            return Ok(Priority::default());
+        }
+        let choice: u32 = input.parse().unwrap_or(0);
+        
+        match choice {
+            1 => return Ok(Priority::Low),
+            2 => return Ok(Priority::Medium),
+            3 => return Ok(Priority::High),
+            4 => return Ok(Priority::Critical),
+            _ => println!("{}", tr("common.choose_1_to_4")),
+        }
     }
+}
 
-    fn add_item_with_details_status(&mut self, title: String, description: Option<String>, status: TaskStatus, priority: Priority, due_date: Option<NaiveDate>) {
-        let id = self.next_id();
-        let item = TodoItem {
-            id,
-            title,
-            description,
-            status,
-            priority,
-            due_date,
-            created_at: Utc::now(),
-            completed_at: if status.is_done() { Some(Utc::now()) } else { None },
-        };
-        self.items.push(item);
-        self.last_modified = Utc::now();
+fn get_recurrence_from_user() -> io::Result<Option<Recurrence>> {
+    println!("Cette tâche se répète-t-elle? (Entrée = non)");
+    println!("1 - Non");
+    println!("2 - Tous les jours");
+    println!("3 - Toutes les semaines");
+    println!("4 - Tous les mois");
+    loop {
+        let input = read_line()?;
+        match input.as_str() {
+            "" | "1" => return Ok(None),
+            "2" => return Ok(Some(Recurrence::Daily)),
+            "3" => return Ok(Some(Recurrence::Weekly)),
+            "4" => return Ok(Some(Recurrence::Monthly)),
+            _ => println!("{}", tr("common.choose_1_to_4")),
+        }
     }
+}
 
-    fn remove_item(&mut self, id: u32) -> bool {
-        if let Some(index) = self.items.iter().position(|item| item.id == id) {
-            self.items.remove(index);
-            self.last_modified = Utc::now();
-            true
-        } else {
-            false
+fn get_label_color_from_user() -> io::Result<Option<LabelColor>> {
+    println!("Couleur de la catégorie (Entrée = aucune):");
+    println!("1 - Aucune");
+    for (i, color) in LabelColor::ALL.iter().enumerate() {
+        println!("{} - {}", i + 2, color.to_string());
+    }
+    loop {
+        let input = read_line()?;
+        if input.is_empty() || input == "1" {
+            return Ok(None);
+        }
+        match input.parse::<usize>().ok().and_then(|choice| LabelColor::ALL.get(choice.checked_sub(2)?)) {
+            Some(color) => return Ok(Some(*color)),
+            None => println!("Choix invalide. Veuillez entrer un nombre entre 1 et {}.", LabelColor::ALL.len() + 1),
         }
     }
+}
 
-    fn toggle_item(&mut self, id: u32) -> bool {
-        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
-            if item.status.is_done() {
-                item.mark_incomplete();
-            } else {
-                item.mark_completed();
-            }
-            self.last_modified = Utc::now();
-            true
-        } else {
-            false
+fn get_sort_key_from_user() -> io::Result<SortKey> {
+    let keys = [SortKey::Priority, SortKey::DueDate, SortKey::Id, SortKey::CreatedAt, SortKey::Status, SortKey::Title, SortKey::Manual];
+    println!("Choisissez le tri:");
+    for (i, key) in keys.iter().enumerate() {
+        println!("{} - {}", i + 1, key.to_string());
+    }
+    loop {
+        let choice = read_number()? as usize;
+        if choice > 0 && choice <= keys.len() {
+            return Ok(keys[choice - 1]);
         }
+        println!("Choix invalide. Veuillez entrer un nombre entre 1 et {}.", keys.len());
     }
+}
 
-    fn update_item_priority(&mut self, id: u32, priority: Priority) -> bool {
-        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
-            item.priority = priority;
-            self.last_modified = Utc::now();
-            true
-        } else {
-            false
+fn get_item_filter_from_user() -> io::Result<ItemFilter> {
+    let filters = [ItemFilter::All, ItemFilter::Incomplete, ItemFilter::Completed];
+    println!("Quels éléments afficher?");
+    for (i, filter) in filters.iter().enumerate() {
+        println!("{} - {}", i + 1, filter.to_string());
+    }
+    loop {
+        let choice = read_number()? as usize;
+        if choice > 0 && choice <= filters.len() {
+            return Ok(filters[choice - 1]);
         }
+        println!("Choix invalide. Veuillez entrer un nombre entre 1 et {}.", filters.len());
     }
+}
 
-    fn update_item_due_date(&mut self, id: u32, due_date: Option<NaiveDate>) -> bool {
-        if let Some(item) = self.items.iter_mut().find(|item| item.id == id) {
-            item.due_date = due_date;
-            self.last_modified = Utc::now();
-            true
-        } else {
-            false
+fn get_due_date_from_user() -> io::Result<Option<NaiveDate>> {
+    if !confirm("Voulez-vous ajouter une date d'échéance?") {
+        return Ok(None);
+    }
+    
+    let date_format = config().date_format();
+    println!(
+        "Entrez la date d'échéance ({}, ou aujourd'hui, demain, +3j, +1s, +1m; laisser vide pour annuler):",
+        date_format_hint(date_format)
+    );
+    loop {
+        let date_input = read_line()?;
+        if date_input.is_empty() {
+            return Ok(None);
+        }
+
+        let Some(date) = parse_due(&date_input, date_format) else {
+            let example = NaiveDate::from_ymd_opt(2024, 12, 25).expect("date valide");
+            println!(
+                "Date non reconnue. Utilisez {} (ex: {}) ou un raccourci comme demain ou +3j:",
+                date_format_hint(date_format),
+                example.format(date_format)
+            );
+            continue;
+        };
+        let today = Local::now().date_naive();
+        if date < today {
+            println!("⚠️  Attention: Cette date est dans le passé!");
+            if !confirm("Voulez-vous continuer?") {
+                return Ok(None);
+            }
         }
+        println!("Échéance: {}", format_date(date));
+        return Ok(Some(date));
     }
+}
 
-    fn display(&self) {
-        println!("\n=== {} ===", self.name);
-        println!("Créée le: {}", self.created_at.format("%d/%m/%Y à %H:%M"));
-        println!("Dernière modification: {}", self.last_modified.format("%d/%m/%Y à %H:%M"));
-        println!("Nombre d'éléments: {}", self.items.len());
-        println!();
-        
-        if self.items.is_empty() {
-            println!("Aucun élément dans cette liste.");
-        } else {
-            // Trier les éléments par priorité (critique en premier) puis par date d'échéance
-            let mut sorted_items = self.items.clone();
-            sorted_items.sort_by(|a, b| {
-                // D'abord par priorité (Critical > High > Medium > Low)
-                let priority_cmp = (b.priority as u8).cmp(&(a.priority as u8));
-                if priority_cmp != std::cmp::Ordering::Equal {
-                    return priority_cmp;
-                }
-                
-                // Puis par date d'échéance (plus tôt en premier)
-                match (a.due_date, b.due_date) {
-                    (Some(a_date), Some(b_date)) => a_date.cmp(&b_date),
-                    (Some(_), None) => std::cmp::Ordering::Less,
-                    (None, Some(_)) => std::cmp::Ordering::Greater,
-                    (None, None) => std::cmp::Ordering::Equal,
-                }
-            });
-
-            for item in &sorted_items {
-                let status_symbol = item.status.to_symbol();
-                let status_text = item.status.to_string();
-                let priority_symbol = item.priority.to_symbol();
-                let priority_text = item.priority.to_string();
-                println!("{} [{}] {} ({}) - {} {}", status_symbol, item.id, item.title, priority_text, status_text, priority_symbol);
-                
-                if let Some(desc) = &item.description {
-                    println!("    Description: {}", desc);
-                }
-                
-                if let Some(due_date) = item.due_date {
-                    let date_str = due_date.format("%d/%m/%Y").to_string();
-                    if item.is_overdue() {
-                        println!("    ⚠️  ÉCHÉANCE DÉPASSÉE: {}", date_str);
-                    } else if let Some(days) = item.days_until_due() {
-                        if days == 0 {
-                            println!("    📅 Échéance: {} (AUJOURD'HUI!)", date_str);
-                        } else if days == 1 {
-                            println!("    📅 Échéance: {} (DEMAIN)", date_str);
-                        } else if days < 7 {
-                            println!("    📅 Échéance: {} (dans {} jours)", date_str, days);
-                        } else {
-                            println!("    📅 Échéance: {}", date_str);
-                        }
-                    }
-                }
-                
-                if item.status.is_done() {
-                    if let Some(completed_at) = item.completed_at {
-                        println!("    ✅ Terminé le: {}", completed_at.format("%d/%m/%Y à %H:%M"));
-                    }
+// période (jours compris) choisie parmi les raccourcis ou saisie; None si une date est invalide
+fn get_period_from_user() -> io::Result<Option<(NaiveDate, NaiveDate)>> {
+    println!("Période:");
+    println!("1 - Cette semaine");
+    println!("2 - Ce mois-ci");
+    println!("3 - Autre (deux dates)");
+    let today = Local::now().date_naive();
+    loop {
+        match read_number()? {
+            1 => {
+                let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                return Ok(Some((monday, today)));
+            }
+            2 => return Ok(Some((today.with_day(1).unwrap_or(today), today))),
+            3 => {
+                let Some(start) = read_date("Entrez la date de début")? else {
+                    return Ok(None);
+                };
+                let Some(end) = read_date("Entrez la date de fin")? else {
+                    return Ok(None);
+                };
+                if end < start {
+                    println!("La date de fin doit être postérieure à la date de début.");
+                    return Ok(None);
                 }
-                println!();
+                return Ok(Some((start, end)));
             }
+            _ => println!("Choix invalide. Entrez 1, 2 ou 3."),
         }
     }
 }
 
-fn save_todo_list(todo_list: &TodoList) -> Result<(), Box<dyn std::error::Error>> {
-    let filename = format!("{}.json", todo_list.name.replace(" ", "_").to_lowercase());
-    let json = serde_json::to_string_pretty(todo_list)?;
-    fs::write(filename, json)?;
-    println!("Liste '{}' sauvegardée avec succès!", todo_list.name);
-    Ok(())
+fn read_date(prompt: &str) -> io::Result<Option<NaiveDate>> {
+    let date_format = config().date_format();
+    println!("{} (format: {}):", prompt, date_format_hint(date_format));
+    match NaiveDate::parse_from_str(&read_line()?, date_format) {
+        Ok(date) => Ok(Some(date)),
+        Err(_) => {
+            println!("Format de date invalide. Utilisez {}", date_format_hint(date_format));
+            Ok(None)
+        }
+    }
 }
 
-fn load_todo_list(name: &str) -> Result<TodoList, Box<dyn std::error::Error>> {
-    let filename = format!("{}.json", name.replace(" ", "_").to_lowercase());
-    let content = fs::read_to_string(filename)?;
-    let todo_list: TodoList = serde_json::from_str(&content)?;
-    Ok(todo_list)
+// bornes d'une journée locale, converties en UTC comme les dates de complétion
+fn local_start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    let start = date.and_hms_opt(0, 0, 0).expect("heure valide");
+    start.and_local_timezone(Local).earliest().map_or_else(|| start.and_utc(), |start| start.with_timezone(&Utc))
 }
 
-fn list_saved_todo_lists() -> Vec<String> {
-    let mut lists = Vec::new();
-    if let Ok(entries) = fs::read_dir(".") {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                if let Some(filename) = entry.file_name().to_str() {
-                    if filename.ends_with(".json") {
-                        let name = filename.replace(".json", "").replace("_", " ");
-                        lists.push(name);
-                    }
-                }
-            }
-        }
+fn local_end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    match date.succ_opt() {
+        Some(next_day) => local_start_of_day(next_day) - Duration::nanoseconds(1),
+        None => DateTime::<Utc>::MAX_UTC,
     }
-    lists
 }
 
-fn get_priority_from_user() -> Priority {
-    println!("Choisissez la priorité:");
-    println!("1 - Basse 🟢");
-    println!("2 - Moyenne 🟡");
-    println!("3 - Haute 🟠");
-    println!("4 - Critique 🔴");
-    
+fn get_status_from_user() -> io::Result<TaskStatus> {
+    println!("Choisissez l'état de la tâche :");
+    let statuses = [TaskStatus::Afaire, TaskStatus::EnCours, TaskStatus::EnAttente, TaskStatus::Terminee];
+    for (i, status) in statuses.iter().enumerate() {
+        println!("{} - {} {}", i + 1, status.to_string(), status.to_symbol());
+    }
     loop {
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).expect("Erreur de lecture");
-        let choice: u32 = input.trim().parse().unwrap_or(0);
-        
+        let input = read_line()?;
+        let choice: u32 = input.parse().unwrap_or(0);
         match choice {
-            1 => return Priority::Low,
-            2 => return Priority::Medium,
-            3 => return Priority::High,
-            4 => return Priority::Critical,
-            _ => println!("Choix invalide. Veuillez entrer 1, 2, 3 ou 4."),
+            1 => return Ok(TaskStatus::Afaire),
+            2 => return Ok(TaskStatus::EnCours),
+            3 => return Ok(TaskStatus::EnAttente),
+            4 => return Ok(TaskStatus::Terminee),
+            _ => println!("{}", tr("common.choose_1_to_4")),
         }
     }
 }
 
-fn get_due_date_from_user() -> Option<NaiveDate> {
-    println!("Voulez-vous ajouter une date d'échéance? (oui/non)");
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Erreur de lecture");
-    
-    if input.trim().to_lowercase() != "oui" {
-        return None;
+// verrouille la liste à écrire avant de vérifier si elle existe, pour qu'une autre instance ne
+// puisse pas la créer entre les deux; deux noms différents peuvent donner le même fichier, d'où
+// la confirmation. L'historique d'une liste écrasée est mis de côté. None si l'action est annulée
+fn lock_for_overwrite(name: &str, cancelled: &str) -> io::Result<Option<ListLock>> {
+    let Some(lock) = acquire_lock(name)? else {
+        return Ok(None);
+    };
+    if list_exists(name) && !confirm("Une liste portant ce nom existe déjà. Écraser?") {
+        println!("{}", cancelled);
+        return Ok(None);
     }
-    
-    println!("Entrez la date d'échéance (format: JJ/MM/AAAA):");
-    let mut date_input = String::new();
-    io::stdin().read_line(&mut date_input).expect("Erreur de lecture");
-    
-    let date_str = date_input.trim();
-    match NaiveDate::parse_from_str(date_str, "%d/%m/%Y") {
-        Ok(date) => {
-            let today = Local::now().date_naive();
-            if date < today {
-                println!("⚠️  Attention: Cette date est dans le passé!");
-                println!("Voulez-vous continuer? (oui/non)");
-                let mut confirm = String::new();
-                io::stdin().read_line(&mut confirm).expect("Erreur de lecture");
-                if confirm.trim().to_lowercase() != "oui" {
-                    return None;
-                }
+    match rotate_history(name) {
+        Ok(Some(path)) => println!("L'historique de l'ancienne liste est conservé dans {}.", path.display()),
+        Ok(None) => {}
+        Err(e) => println!("Impossible de mettre de côté l'ancien historique: {}", e),
+    }
+    Ok(Some(lock))
+}
+
+fn get_snooze_duration_from_user() -> io::Result<Duration> {
+    println!("Reporter de:");
+    println!("1 - 1 jour");
+    println!("2 - 1 semaine");
+    println!("3 - Autre (nombre de jours)");
+    loop {
+        match read_number()? {
+            1 => return Ok(Duration::days(1)),
+            2 => return Ok(Duration::weeks(1)),
+            3 => {
+                println!("Entrez le nombre de jours:");
+                return Ok(Duration::days(read_number()? as i64));
             }
-            Some(date)
+            _ => println!("Choix invalide. Entrez 1, 2 ou 3."),
         }
-        Err(_) => {
-            println!("Format de date invalide. Utilisez JJ/MM/AAAA (ex: 25/12/2024)");
-            None
+    }
+}
+
+fn get_estimate_from_user() -> io::Result<Option<u32>> {
+    println!("Temps estimé en minutes (optionnel, appuyez sur Entrée pour passer):");
+    loop {
+        let input = read_line()?;
+        if input.is_empty() {
+            return Ok(None);
+        }
+        match input.parse::<u32>() {
+            Ok(minutes) => return Ok(Some(minutes)),
+            Err(_) => println!("Veuillez entrer un nombre de minutes valide."),
         }
     }
 }
 
-fn get_status_from_user() -> TaskStatus {
-    println!("Choisissez l'état de la tâche :");
-    println!("1 - À faire ⬜");
-    println!("2 - En cours 🟦");
-    println!("3 - En attente 🟨");
-    println!("4 - Terminée ✅");
+fn get_points_from_user() -> io::Result<Option<u8>> {
+    println!("Points d'effort (optionnel, 0-255, appuyez sur Entrée pour passer):");
     loop {
-        let mut input = String::new();
-        io::stdin().read_line(&mut input).expect("Erreur de lecture");
-        let choice: u32 = input.trim().parse().unwrap_or(0);
-        match choice {
-            1 => return TaskStatus::Afaire,
-            2 => return TaskStatus::EnCours,
-            3 => return TaskStatus::EnAttente,
-            4 => return TaskStatus::Terminee,
-            _ => println!("Choix invalide. Veuillez entrer 1, 2, 3 ou 4."),
+        let input = read_line()?;
+        if input.is_empty() {
+            return Ok(None);
         }
+        match input.parse::<u8>() {
+            Ok(points) => return Ok(Some(points)),
+            Err(_) => println!("Veuillez entrer un nombre de points entre 0 et 255."),
+        }
+    }
+}
+
+fn get_assignee_from_user() -> io::Result<Option<String>> {
+    println!("Responsable (optionnel, appuyez sur Entrée pour passer):");
+    let input = read_line()?;
+    let who = input.trim_start_matches('@').trim();
+    Ok(if who.is_empty() { None } else { Some(who.to_string()) })
+}
+
+fn get_tags_from_user() -> io::Result<Vec<String>> {
+    println!("Entrez des tags séparés par des virgules (optionnel, ex: travail, maison):");
+    let input = read_line()?;
+    Ok(parse_tags(&input))
+}
+
+// demande les détails d'un nouvel élément puis l'ajoute à la liste
+fn add_item_from_user(todo_list: &mut TodoList, title: String) -> io::Result<()> {
+    if todo_list.find_by_title(&title).is_some() && !confirm("Une tâche identique existe déjà. Ajouter quand même?") {
+        println!("Ajout annulé.");
+        return Ok(());
     }
+
+    println!("Entrez une description (optionnel, appuyez sur Entrée pour passer, '+' pour plusieurs lignes):");
+    let description = read_description()?;
+    
+    let desc = if description.is_empty() { None } else { Some(description) };
+    let status = get_status_from_user()?;
+    let priority = get_priority_from_user()?;
+    let due_date = get_due_date_from_user()?;
+    let tags = get_tags_from_user()?;
+    let assignee = get_assignee_from_user()?;
+    let recurrence = get_recurrence_from_user()?;
+    let estimate_minutes = get_estimate_from_user()?;
+    let points = get_points_from_user()?;
+    
+    let item = todo_list.add_item_with_details_status(title, desc, status, priority, due_date);
+    item.tags = tags;
+    item.recurrence = recurrence;
+    item.estimate_minutes = estimate_minutes;
+    item.points = points;
+    item.assignee = assignee;
+    println!("Élément ajouté!");
+    Ok(())
 }
 
-fn creer_liste() {
+fn creer_liste() -> io::Result<()> {
     println!("Entrez le nom de votre nouvelle todo list:");
-    let mut name = String::new();
-    io::stdin().read_line(&mut name).expect("Erreur de lecture");
-    let name = name.trim().to_string();
+    let name = read_line()?;
     
-    if name.is_empty() {
-        println!("Le nom ne peut pas être vide!");
-        return;
+    if sanitize_name(&name).is_empty() {
+        println!("Le nom doit contenir au moins une lettre ou un chiffre!");
+        return Ok(());
     }
+
+    let Some(_lock) = lock_for_overwrite(&name, "Création annulée.")? else {
+        return Ok(());
+    };
     
     let mut todo_list = TodoList::new(name.clone());
     
     println!("Liste '{}' créée! Ajoutons quelques éléments:", name);
     
+    // même interrompue (Ctrl-D), la saisie garde les éléments déjà entrés
+    let result = add_items_from_user(&mut todo_list);
+    
+    todo_list.display();
+    
+    if let Err(e) = save_list(&mut todo_list, config().verbosity) {
+        println!("Erreur lors de la sauvegarde: {}", e);
+    }
+    result
+}
+
+fn add_items_from_user(todo_list: &mut TodoList) -> io::Result<()> {
     loop {
         println!("\nEntrez le titre de l'élément (ou 'fin' pour terminer):");
-        let mut title = String::new();
-        io::stdin().read_line(&mut title).expect("Erreur de lecture");
-        let title = title.trim().to_string();
+        let title = read_line()?;
         
         if title.to_lowercase() == "fin" {
-            break;
+            return Ok(());
         }
         
         if title.is_empty() {
@@ -426,286 +590,2109 @@ fn creer_liste() {
             continue;
         }
         
-        println!("Entrez une description (optionnel, appuyez sur Entrée pour passer):");
-        let mut description = String::new();
-        io::stdin().read_line(&mut description).expect("Erreur de lecture");
-        let description = description.trim().to_string();
-        
-        let desc = if description.is_empty() { None } else { Some(description) };
-        let status = get_status_from_user();
-        let priority = get_priority_from_user();
-        let due_date = get_due_date_from_user();
-        
-        todo_list.add_item_with_details_status(title, desc, status, priority, due_date);
-        println!("Élément ajouté!");
-    }
-    
-    todo_list.display();
-    
-    if let Err(e) = save_todo_list(&todo_list) {
-        println!("Erreur lors de la sauvegarde: {}", e);
+        add_item_from_user(todo_list, title)?;
     }
 }
 
-fn mettre_a_jour_liste() {
-    let lists = list_saved_todo_lists();
+// au-delà de ce nombre de listes, on propose de filtrer avant d'afficher
+const LIST_FILTER_THRESHOLD: usize = 10;
+
+// nombre de lignes du terminal ($LINES), moins la place des messages autour de la liste
+fn page_size() -> usize {
+    let lines = env::var("LINES").ok().and_then(|lines| lines.trim().parse::<usize>().ok()).unwrap_or(24);
+    lines.saturating_sub(4).max(5)
+}
+
+// affiche les listes sauvegardées (filtrées et paginées s'il y en a beaucoup)
+// et renvoie celle choisie par l'utilisateur
+fn choose_list(action: &str) -> io::Result<Option<String>> {
+    let mut lists = list_saved_todo_lists();
     
     if lists.is_empty() {
         println!("Aucune liste sauvegardée trouvée.");
-        return;
+        return Ok(None);
+    }
+
+    if lists.len() > LIST_FILTER_THRESHOLD {
+        println!("{} listes sauvegardées. Filtrer par nom (Entrée pour toutes les afficher):", lists.len());
+        let filter = read_line()?.to_lowercase();
+        lists.retain(|list_name| list_name.to_lowercase().contains(&filter));
+        if lists.is_empty() {
+            println!("Aucune liste ne correspond à '{}'.", filter);
+            return Ok(None);
+        }
     }
     
     println!("Listes disponibles:");
-    for (i, list_name) in lists.iter().enumerate() {
-        println!("{} - {}", i + 1, list_name);
+    let page_size = page_size();
+    let mut choice = String::new();
+    for (page, chunk) in lists.chunks(page_size).enumerate() {
+        for (i, list_name) in chunk.iter().enumerate() {
+            println!("{} - {}", page * page_size + i + 1, list_name);
+        }
+        if (page + 1) * page_size < lists.len() {
+            println!("-- Entrée pour la suite, ou numéro de la liste à {} --", action);
+            choice = read_line()?;
+            if !choice.is_empty() {
+                break;
+            }
+        }
     }
     
-    println!("Choisissez le numéro de la liste à modifier:");
-    let mut choice = String::new();
-    io::stdin().read_line(&mut choice).expect("Erreur de lecture");
-    let choice: usize = choice.trim().parse().expect("Veuillez entrer un nombre");
+    if choice.is_empty() {
+        println!("Choisissez le numéro de la liste à {}:", action);
+        choice = read_number()?.to_string();
+    }
     
-    if choice > 0 && choice <= lists.len() {
-        let list_name = &lists[choice - 1];
-        
-        match load_todo_list(list_name) {
-            Ok(mut todo_list) => {
-                todo_list.display();
-                modifier_liste(&mut todo_list);
-                if let Err(e) = save_todo_list(&todo_list) {
-                    println!("Erreur lors de la sauvegarde: {}", e);
+    match choice.parse::<usize>() {
+        Ok(choice) if choice > 0 && choice <= lists.len() => Ok(Some(lists[choice - 1].clone())),
+        _ => {
+            println!("{}", tr("common.invalid_choice"));
+            Ok(None)
+        }
+    }
+}
+
+fn mettre_a_jour_liste() -> io::Result<()> {
+    let Some(list_name) = choose_list("modifier")? else {
+        return Ok(());
+    };
+    editer_liste(&list_name)
+}
+
+fn reprendre_derniere_liste() -> io::Result<()> {
+    let Some(list_name) = last_list_name() else {
+        println!("Aucune liste récente, choisissez-en une.");
+        return mettre_a_jour_liste();
+    };
+    if !list_exists(&list_name) {
+        println!("La liste '{}' n'existe plus, choisissez-en une autre.", list_name);
+        return mettre_a_jour_liste();
+    }
+    editer_liste(&list_name)
+}
+
+// verrouille la liste le temps de l'édition; None si elle est déjà ouverte ailleurs
+// et que l'utilisateur ne force pas l'ouverture
+fn acquire_lock(list_name: &str) -> io::Result<Option<ListLock>> {
+    match lock_list(list_name, false) {
+        Ok(lock) => Ok(Some(lock)),
+        Err(LockError::Locked(holder)) => {
+            println!("Impossible de modifier '{}': {}.", list_name, LockError::Locked(holder.clone()));
+            match holder.is_stale() {
+                Some(true) => println!("Ce processus n'existe plus: le verrou est sans doute resté après un arrêt brutal."),
+                Some(false) => println!("Une autre instance de ToDoCLI semble modifier cette liste; ses modifications pourraient être écrasées."),
+                None => println!("Si aucune autre instance n'est ouverte, le verrou est resté après un arrêt brutal."),
+            }
+            if !confirm("Passer outre le verrou?") {
+                println!("Action annulée.");
+                return Ok(None);
+            }
+            match lock_list(list_name, true) {
+                Ok(lock) => Ok(Some(lock)),
+                Err(e) => {
+                    println!("Erreur: {}", e);
+                    Ok(None)
                 }
             }
-            Err(e) => println!("Erreur lors du chargement: {}", e),
         }
-    } else {
-        println!("Choix invalide.");
+        Err(e) => {
+            println!("Erreur: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+// pour les actions qui peuvent réécrire n'importe quelle liste (restauration d'une sauvegarde):
+// les listes existantes et celles qui vont être créées, chacune verrouillée une seule fois
+fn acquire_all_locks(new_names: &[String]) -> io::Result<Option<Vec<ListLock>>> {
+    let mut names = BTreeMap::new();
+    for list_name in list_saved_todo_lists().into_iter().chain(new_names.iter().cloned()) {
+        names.entry(sanitize_name(&list_name)).or_insert(list_name);
+    }
+    let mut locks = Vec::new();
+    for list_name in names.values() {
+        let Some(lock) = acquire_lock(list_name)? else {
+            return Ok(None);
+        };
+        locks.push(lock);
+    }
+    Ok(Some(locks))
+}
+
+fn editer_liste(list_name: &str) -> io::Result<()> {
+    let Some(_lock) = acquire_lock(list_name)? else {
+        return Ok(());
+    };
+    if let Some(mut todo_list) = open_todo_list(list_name)? {
+        todo_list.display();
+        let result = modifier_liste(&mut todo_list);
+        // saisie interrompue (Ctrl-D): on sauvegarde sans demander pour ne rien perdre
+        if result.is_err()
+            && todo_list.is_dirty()
+            && let Err(e) = save_list(&mut todo_list, config().verbosity)
+        {
+            println!("Erreur lors de la sauvegarde: {}", e);
+        }
+        return result;
+    }
+    Ok(())
+}
+
+// vérifié avant chaque affichage du menu, donc après chaque action, quelle qu'en soit l'issue
+fn autosave(todo_list: &mut TodoList) {
+    let every = config().autosave_every;
+    if every == 0 || todo_list.unsaved_changes() < every {
+        return;
+    }
+    match save_todo_list(todo_list) {
+        Ok(_) => {
+            if config().verbosity.shows_success() {
+                println!("(sauvegarde automatique)");
+            }
+        }
+        Err(e) => println!("Erreur lors de la sauvegarde automatique: {}", e),
+    }
+}
+
+// entrées du menu de modification (clés du catalogue), dans l'ordre des numéros
+const MODIFY_MENU: [&str; 38] = [
+    "modify.add_item",
+    "modify.change_status",
+    "modify.toggle",
+    "modify.remove",
+    "modify.priority",
+    "modify.due_date",
+    "modify.display",
+    "modify.display_sorted",
+    "modify.edit_item",
+    "modify.filter_tag",
+    "modify.archive_completed",
+    "modify.show_archives",
+    "modify.complete_many",
+    "modify.add_subtask",
+    "modify.toggle_subtask",
+    "modify.move_item",
+    "modify.filter_status",
+    "modify.add_note",
+    "modify.clear_completed",
+    "modify.star",
+    "modify.workload",
+    "modify.filter_assignee",
+    "modify.advance",
+    "modify.item_detail",
+    "modify.bulk_add",
+    "modify.metadata",
+    "modify.postpone",
+    "modify.next_task",
+    "modify.renumber",
+    "modify.completed_between",
+    "modify.category",
+    "modify.by_category",
+    "modify.reset_view",
+    "modify.links",
+    "modify.follow_up",
+    "modify.by_priority",
+    "modify.postpone_overdue",
+    "modify.back",
+];
+
+// pour les actions qui ont besoin d'au moins un élément: prévient et renvoie false si la liste est vide
+fn has_items(todo_list: &TodoList) -> bool {
+    if todo_list.items.is_empty() {
+        println!("{}", tr("common.list_empty"));
+        return false;
+    }
+    true
+}
+
+// lance `action` avec la règle des sous-tâches de config.json; si elle refuse de terminer l'élément,
+// rappelle les sous-tâches restantes et propose de le terminer quand même
+fn with_subtask_rule<T>(mut action: impl FnMut(SubtaskRule) -> Result<T, ToggleError>) -> Result<T, ToggleError> {
+    match action(config().subtask_rule()) {
+        Err(ToggleError::PendingSubtasks(pending)) => {
+            println!("Sous-tâches non terminées:");
+            for title in &pending {
+                println!("  ☐ {}", title);
+            }
+            if confirm("Terminer quand même?") {
+                action(SubtaskRule::Ignore)
+            } else {
+                Err(ToggleError::PendingSubtasks(pending))
+            }
+        }
+        result => result,
     }
 }
 
-fn modifier_liste(todo_list: &mut TodoList) {
+// nombre d'entrées du menu de modification, pour la sélection à une touche
+const MODIFY_MENU_OPTIONS: u32 = MODIFY_MENU.len() as u32;
+
+fn modifier_liste(todo_list: &mut TodoList) -> io::Result<()> {
     loop {
-        println!("\nActions disponibles:");
-        println!("1 - Ajouter un élément");
-        println!("2 - Changer l'état d'un élément");
-        println!("3 - Marquer un élément comme terminé/non terminé");
-        println!("4 - Supprimer un élément");
-        println!("5 - Modifier la priorité d'un élément");
-        println!("6 - Modifier la date d'échéance d'un élément");
-        println!("7 - Afficher la liste");
-        println!("8 - Retour au menu principal");
+        report_compaction(todo_list, config().verbosity);
+        autosave(todo_list);
+        println!("\n{}", tr("modify.title"));
+        for (i, key) in MODIFY_MENU.iter().enumerate() {
+            println!("{} - {}", i + 1, tr(key));
+        }
         
-        let mut choice = String::new();
-        io::stdin().read_line(&mut choice).expect("Erreur de lecture");
-        let choice: u32 = choice.trim().parse().expect("Veuillez entrer un nombre");
+        let Some(choice) = read_menu_selection(MODIFY_MENU_OPTIONS)? else {
+            println!("{}", tr("common.invalid_number"));
+            continue;
+        };
         
         match choice {
             1 => {
                 println!("Entrez le titre de l'élément:");
-                let mut title = String::new();
-                io::stdin().read_line(&mut title).expect("Erreur de lecture");
-                let title = title.trim().to_string();
+                let title = read_line()?;
                 
                 if title.is_empty() {
                     println!("Le titre ne peut pas être vide!");
                     continue;
                 }
                 
-                println!("Entrez une description (optionnel):");
-                let mut description = String::new();
-                io::stdin().read_line(&mut description).expect("Erreur de lecture");
-                let description = description.trim().to_string();
-                
-                let desc = if description.is_empty() { None } else { Some(description) };
-                let status = get_status_from_user();
-                let priority = get_priority_from_user();
-                let due_date = get_due_date_from_user();
-                
-                todo_list.add_item_with_details_status(title, desc, status, priority, due_date);
-                println!("Élément ajouté!");
+                add_item_from_user(todo_list, title)?;
             }
             2 => {
-                if todo_list.items.is_empty() {
-                    println!("La liste est vide!");
+                if !has_items(todo_list) {
                     continue;
                 }
                 todo_list.display();
                 println!("Entrez l'ID de l'élément dont vous voulez changer l'état:");
-                let mut id_input = String::new();
-                io::stdin().read_line(&mut id_input).expect("Erreur de lecture");
-                let id: u32 = id_input.trim().parse().expect("Veuillez entrer un nombre");
-                let new_status = get_status_from_user();
-                if let Some(item) = todo_list.items.iter_mut().find(|item| item.id == id) {
-                    item.status = new_status;
-                    if new_status.is_done() {
-                        item.completed_at = Some(Utc::now());
-                    } else {
-                        item.completed_at = None;
-                    }
-                    todo_list.last_modified = Utc::now();
-                    println!("État modifié!");
-                } else {
-                    println!("Élément non trouvé!");
+                let id = read_number()?;
+                let new_status = get_status_from_user()?;
+                match with_subtask_rule(|rule| todo_list.update_item_status(id, new_status, rule)) {
+                    Ok(()) => println!("État modifié!"),
+                    Err(ToggleError::PendingSubtasks(_)) => println!("Action annulée."),
+                    Err(_) => println!("{}", tr("common.item_not_found")),
                 }
             }
             3 => {
-                if todo_list.items.is_empty() {
-                    println!("La liste est vide!");
+                if !has_items(todo_list) {
                     continue;
                 }
                 
                 todo_list.display();
-                println!("Entrez l'ID de l'élément à modifier:");
-                let mut id_input = String::new();
-                io::stdin().read_line(&mut id_input).expect("Erreur de lecture");
-                let id: u32 = id_input.trim().parse().expect("Veuillez entrer un nombre");
+                println!("Entrez l'ID ou le titre de l'élément à modifier:");
+                let Some(id) = read_item_id(todo_list)? else {
+                    println!("{}", tr("common.item_not_found"));
+                    continue;
+                };
+                let Some(title) = todo_list.item_title(id) else {
+                    println!("{}", tr("common.item_not_found"));
+                    continue;
+                };
+                println!("Élément [{}] {}", id, title);
                 
-                if todo_list.toggle_item(id) {
-                    println!("Statut modifié!");
-                } else {
-                    println!("Élément non trouvé!");
+                match with_subtask_rule(|rule| todo_list.toggle_item(id, rule)) {
+                    Ok(()) => println!("Statut modifié!"),
+                    Err(ToggleError::PendingSubtasks(_)) => println!("Action annulée."),
+                    Err(_) => println!("{}", tr("common.item_not_found")),
                 }
             }
             4 => {
-                if todo_list.items.is_empty() {
-                    println!("La liste est vide!");
+                if !has_items(todo_list) {
                     continue;
                 }
                 
                 todo_list.display();
-                println!("Entrez l'ID de l'élément à supprimer:");
-                let mut id_input = String::new();
-                io::stdin().read_line(&mut id_input).expect("Erreur de lecture");
-                let id: u32 = id_input.trim().parse().expect("Veuillez entrer un nombre");
+                println!("Entrez l'ID ou le titre de l'élément à supprimer:");
+                let Some(id) = read_item_id(todo_list)? else {
+                    println!("{}", tr("common.item_not_found"));
+                    continue;
+                };
+                // un ID tapé par erreur (vu dans une autre liste) ne supprime rien sans confirmation
+                let Some(title) = todo_list.item_title(id) else {
+                    println!("{}", tr("common.item_not_found"));
+                    continue;
+                };
+                if !confirm(&format!("Supprimer l'élément [{}] {}?", id, title)) {
+                    println!("Suppression annulée.");
+                    continue;
+                }
                 
                 if todo_list.remove_item(id) {
                     println!("Élément supprimé!");
                 } else {
-                    println!("Élément non trouvé!");
+                    println!("{}", tr("common.item_not_found"));
                 }
             }
             5 => {
-                if todo_list.items.is_empty() {
-                    println!("La liste est vide!");
+                if !has_items(todo_list) {
                     continue;
                 }
                 
                 todo_list.display();
                 println!("Entrez l'ID de l'élément dont vous voulez modifier la priorité:");
-                let mut id_input = String::new();
-                io::stdin().read_line(&mut id_input).expect("Erreur de lecture");
-                let id: u32 = id_input.trim().parse().expect("Veuillez entrer un nombre");
+                let id = read_number()?;
                 
-                let new_priority = get_priority_from_user();
+                let new_priority = get_priority_from_user()?;
                 if todo_list.update_item_priority(id, new_priority) {
                     println!("Priorité modifiée!");
                 } else {
-                    println!("Élément non trouvé!");
+                    println!("{}", tr("common.item_not_found"));
                 }
             }
             6 => {
-                if todo_list.items.is_empty() {
-                    println!("La liste est vide!");
+                if !has_items(todo_list) {
                     continue;
                 }
                 
                 todo_list.display();
                 println!("Entrez l'ID de l'élément dont vous voulez modifier la date d'échéance:");
-                let mut id_input = String::new();
-                io::stdin().read_line(&mut id_input).expect("Erreur de lecture");
-                let id: u32 = id_input.trim().parse().expect("Veuillez entrer un nombre");
+                let id = read_number()?;
                 
-                let new_due_date = get_due_date_from_user();
+                let new_due_date = get_due_date_from_user()?;
                 if todo_list.update_item_due_date(id, new_due_date) {
                     println!("Date d'échéance modifiée!");
                 } else {
-                    println!("Élément non trouvé!");
+                    println!("{}", tr("common.item_not_found"));
                 }
             }
             7 => {
                 todo_list.display();
             }
-            8 => break,
-            _ => println!("Choix invalide."),
-        }
-    }
-}
+            8 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
 
-fn supprimer_liste() {
-    let lists = list_saved_todo_lists();
-    
-    if lists.is_empty() {
-        println!("Aucune liste sauvegardée trouvée.");
-        return;
-    }
-    
-    println!("Listes disponibles:");
-    for (i, list_name) in lists.iter().enumerate() {
-        println!("{} - {}", i + 1, list_name);
+                let key = get_sort_key_from_user()?;
+                todo_list.update_view(|prefs| prefs.sort = Some(key));
+                todo_list.display();
+            }
+            9 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément à modifier:");
+                let id = read_number()?;
+
+                println!("Nouveau titre (Entrée pour conserver l'actuel):");
+                let title = read_line()?;
+                let new_title = if title.is_empty() { None } else { Some(title) };
+
+                println!("Nouvelle description (Entrée pour conserver l'actuelle, '-' pour l'effacer, '+' pour plusieurs lignes):");
+                let description = read_description()?;
+                let new_description = match description.as_str() {
+                    "" => None,
+                    "-" => Some(None),
+                    _ => Some(Some(description)),
+                };
+
+                if todo_list.edit_item(id, new_title, new_description) {
+                    println!("Élément modifié!");
+                } else {
+                    println!("{}", tr("common.item_not_found"));
+                }
+            }
+            10 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                println!("Entrez le tag à afficher:");
+                let tag = read_line()?;
+                todo_list.display_filtered(&tag);
+            }
+            11 => {
+                let count = todo_list.archive_completed();
+                if count == 0 {
+                    println!("Aucun élément terminé à archiver.");
+                } else {
+                    println!("{} élément(s) archivé(s)!", count);
+                }
+            }
+            12 => {
+                todo_list.display_archives();
+            }
+            13 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez les IDs à terminer (ex: 2-5,8):");
+                let input = read_line()?;
+                let Some(ids) = parse_id_ranges(&input) else {
+                    println!("Saisie invalide. Utilisez des IDs et des plages, ex: 2-5,8");
+                    continue;
+                };
+
+                let not_found: Vec<String> = ids
+                    .iter()
+                    .filter(|id| !todo_list.items.iter().any(|item| item.id == **id))
+                    .map(|id| id.to_string())
+                    .collect();
+                let (count, blocked) = todo_list.toggle_range(&ids, config().subtask_rule());
+                println!("{} élément(s) marqué(s) comme terminé(s).", count);
+                if !not_found.is_empty() {
+                    println!("IDs non trouvés: {}", not_found.join(", "));
+                }
+                if !blocked.is_empty() {
+                    let blocked: Vec<String> = blocked.iter().map(|id| id.to_string()).collect();
+                    println!("Sous-tâches non terminées, éléments ignorés: {}", blocked.join(", "));
+                }
+            }
+            14 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément parent:");
+                let id = read_number()?;
+
+                println!("Entrez le titre de la sous-tâche:");
+                let title = read_line()?;
+
+                if title.is_empty() {
+                    println!("Le titre ne peut pas être vide!");
+                    continue;
+                }
+
+                if todo_list.add_subtask(id, title) {
+                    println!("Sous-tâche ajoutée!");
+                } else {
+                    println!("{}", tr("common.item_not_found"));
+                }
+            }
+            15 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément parent:");
+                let id = read_number()?;
+                println!("Entrez le numéro de la sous-tâche:");
+                let number = read_number()? as usize;
+
+                if todo_list.toggle_subtask(id, number) {
+                    println!("Sous-tâche modifiée!");
+                } else {
+                    println!("Sous-tâche non trouvée!");
+                }
+            }
+            16 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display_sorted(SortKey::Manual);
+                println!("Entrez l'ID de l'élément à déplacer:");
+                let id = read_number()?;
+                println!("Entrez la nouvelle position (1-{}):", todo_list.items.len());
+                let position = read_number()? as usize;
+
+                if todo_list.move_item(id, position) {
+                    println!("Élément déplacé!");
+                } else {
+                    println!("{}", tr("common.item_not_found"));
+                }
+            }
+            17 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                let filter = get_item_filter_from_user()?;
+                todo_list.update_view(|prefs| prefs.filter = (filter != ItemFilter::All).then_some(filter));
+                todo_list.display();
+            }
+            18 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément:");
+                let id = read_number()?;
+
+                println!("Entrez la note:");
+                let text = read_line()?;
+
+                if text.is_empty() {
+                    println!("La note ne peut pas être vide!");
+                    continue;
+                }
+
+                if todo_list.add_note(id, text) {
+                    println!("Note ajoutée!");
+                } else {
+                    println!("{}", tr("common.item_not_found"));
+                }
+            }
+            19 => {
+                let count = todo_list.items.iter().filter(|item| item.status.is_done()).count();
+                if count == 0 {
+                    println!("Aucun élément terminé à supprimer.");
+                    continue;
+                }
+
+                if confirm(&format!("{} élément(s) terminé(s) vont être supprimé(s) définitivement. Confirmer?", count)) {
+                    let removed = todo_list.clear_completed();
+                    println!("{} élément(s) supprimé(s)!", removed);
+                } else {
+                    println!("Suppression annulée.");
+                }
+            }
+            20 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément:");
+                let id = read_number()?;
+
+                if todo_list.toggle_star(id) {
+                    println!("Étoile modifiée!");
+                } else {
+                    println!("{}", tr("common.item_not_found"));
+                }
+            }
+            21 => {
+                let (minutes, unestimated) = todo_list.remaining_estimate();
+                println!("Charge restante estimée: {}", format_minutes(minutes as i64));
+                if unestimated > 0 {
+                    println!("{} élément(s) restant(s) sans estimation.", unestimated);
+                }
+            }
+            22 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                println!("Entrez le nom du responsable:");
+                let who = read_line()?;
+                todo_list.display_by_assignee(&who);
+            }
+            23 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément:");
+                let id = read_number()?;
+
+                match with_subtask_rule(|rule| todo_list.cycle_status(id, rule)) {
+                    Ok(status) => println!("État modifié: {} {}", status.to_symbol(), status.to_string()),
+                    Err(ToggleError::PendingSubtasks(_)) => println!("Action annulée."),
+                    Err(_) => println!("{}", tr("common.item_not_found")),
+                }
+            }
+            24 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                println!("Entrez l'ID de l'élément:");
+                let id = read_number()?;
+                if !todo_list.display_item_detail(id) {
+                    println!("{}", tr("common.item_not_found"));
+                }
+            }
+            25 => {
+                let lines = read_pasted_lines()?;
+                let count = todo_list.add_items_bulk(lines.iter().map(String::as_str));
+                println!("{} élément(s) ajouté(s).", count);
+            }
+            26 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément:");
+                let id = read_number()?;
+                println!("Entrez la clé (ex: url, lieu):");
+                let key = read_line()?;
+                if key.is_empty() {
+                    println!("La clé ne peut pas être vide!");
+                    continue;
+                }
+                println!("Entrez la valeur (laisser vide pour effacer la clé):");
+                let value = read_line()?;
+                let value = (!value.is_empty()).then_some(value);
+                let cleared = value.is_none();
+
+                if !todo_list.set_metadata(id, key, value) {
+                    println!("{}", tr("common.item_not_found"));
+                } else if cleared {
+                    println!("Métadonnée effacée!");
+                } else {
+                    println!("Métadonnée enregistrée!");
+                }
+            }
+            27 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément à reporter:");
+                let id = read_number()?;
+                let by = get_snooze_duration_from_user()?;
+
+                if todo_list.snooze(id, by) {
+                    let due_date = todo_list.items.iter().find(|item| item.id == id).and_then(|item| item.due_date);
+                    if let Some(due_date) = due_date {
+                        println!("Échéance reportée au {}!", format_date(due_date));
+                    }
+                } else {
+                    println!("Élément non trouvé ou sans date d'échéance!");
+                }
+            }
+            28 => match next_task(todo_list) {
+                Some(item) => {
+                    println!("\n{}", section_header("Prochaine tâche"));
+                    item.display();
+                }
+                None => println!("Tout est terminé, bravo! 🎉"),
+            },
+            29 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                println!("⚠️  Les éléments recevront des IDs consécutifs dans l'ordre d'affichage;");
+                println!("l'historique et les notes qui citent les anciens IDs ne seront plus à jour.");
+                if confirm("Renuméroter les éléments?") {
+                    todo_list.renumber();
+                    println!("Éléments renumérotés!");
+                    todo_list.display();
+                } else {
+                    println!("Renumérotation annulée.");
+                }
+            }
+            30 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                let Some((start, end)) = get_period_from_user()? else {
+                    continue;
+                };
+                let items = todo_list.completed_between(local_start_of_day(start), local_end_of_day(end));
+                println!("\n{}", section_header(&format!("Terminés du {} au {}", format_date(start), format_date(end))));
+                if items.is_empty() {
+                    println!("Aucun élément terminé sur cette période.");
+                }
+                for item in &items {
+                    item.display();
+                }
+            }
+            31 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément:");
+                let id = read_number()?;
+                println!("Entrez la catégorie (laisser vide pour la retirer):");
+                let category = read_line()?;
+                let (category, color) = if category.is_empty() {
+                    (None, None)
+                } else {
+                    (Some(category), get_label_color_from_user()?)
+                };
+
+                if todo_list.set_category(id, category, color) {
+                    println!("Catégorie modifiée!");
+                } else {
+                    println!("{}", tr("common.item_not_found"));
+                }
+            }
+            32 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.update_view(|prefs| prefs.grouped = true);
+                todo_list.display();
+            }
+            33 => {
+                todo_list.reset_view();
+                println!("Affichage par défaut rétabli.");
+                todo_list.display();
+            }
+            34 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément:");
+                let id = read_number()?;
+                let Some(item) = todo_list.items.iter().find(|item| item.id == id) else {
+                    println!("{}", tr("common.item_not_found"));
+                    continue;
+                };
+                for (i, link) in item.links.iter().enumerate() {
+                    println!("{}. {}", i + 1, link);
+                }
+                println!("1 - Ajouter un lien");
+                println!("2 - Retirer un lien");
+                match read_line()?.as_str() {
+                    "1" => {
+                        println!("Entrez le chemin ou l'URL:");
+                        if todo_list.add_link(id, &read_line()?) {
+                            println!("Lien ajouté!");
+                        } else {
+                            println!("Le lien ne peut pas être vide!");
+                        }
+                    }
+                    "2" => {
+                        println!("Entrez le numéro du lien à retirer:");
+                        let number = read_number()? as usize;
+                        if todo_list.remove_link(id, number) {
+                            println!("Lien retiré!");
+                        } else {
+                            println!("Lien non trouvé!");
+                        }
+                    }
+                    _ => println!("{}", tr("common.invalid_choice")),
+                }
+            }
+            35 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+
+                todo_list.display();
+                println!("Entrez l'ID de l'élément à terminer:");
+                let id = read_number()?;
+                if !todo_list.items.iter().any(|item| item.id == id) {
+                    println!("{}", tr("common.item_not_found"));
+                    continue;
+                }
+                println!("Titre de la tâche suivante (laisser vide pour annuler):");
+                let title = read_line()?;
+                if title.is_empty() {
+                    println!("Action annulée.");
+                    continue;
+                }
+                println!("Entrez une description (optionnel, appuyez sur Entrée pour passer, '+' pour plusieurs lignes):");
+                let description = read_description()?;
+                let description = (!description.is_empty()).then_some(description);
+
+                match with_subtask_rule(|rule| todo_list.complete_with_follow_up(id, title.clone(), description.clone(), rule)) {
+                    Ok(follow_up_id) => println!("Élément {} terminé, suite ajoutée avec l'ID {}!", id, follow_up_id),
+                    Err(ToggleError::PendingSubtasks(_)) => println!("Action annulée."),
+                    Err(_) => println!("{}", tr("common.item_not_found")),
+                }
+            }
+            36 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+                todo_list.display_by_priority();
+            }
+            37 => {
+                if !has_items(todo_list) {
+                    continue;
+                }
+                let overdue = todo_list.items.iter().filter(|item| item.is_overdue()).count();
+                if overdue == 0 {
+                    println!("Aucun élément en retard!");
+                    continue;
+                }
+                println!("{} élément(s) en retard.", overdue);
+                let by = get_snooze_duration_from_user()?;
+                let count = todo_list.postpone_overdue(by);
+                let still_overdue = todo_list.items.iter().filter(|item| item.is_overdue()).count();
+                println!("{} échéance(s) reportée(s).", count);
+                if still_overdue > 0 {
+                    println!("{} élément(s) restent en retard malgré le report.", still_overdue);
+                }
+            }
+            38 => {
+                if todo_list.is_dirty() {
+                    // pas de confirm ici: la fin de l'entrée doit remonter pour que editer_liste sauvegarde
+                    println!("Des modifications non sauvegardées. Sauvegarder? (oui/non)");
+                    if is_yes(&read_line()?) {
+                        if let Err(e) = save_list(todo_list, config().verbosity) {
+                            println!("Erreur lors de la sauvegarde: {}", e);
+                        }
+                    } else {
+                        println!("Modifications abandonnées.");
+                    }
+                }
+                return Ok(());
+            }
+            _ => println!("{}", tr("common.invalid_choice")),
+        }
+    }
+}
+
+fn rechercher_tache() -> io::Result<()> {
+    println!("Entrez le terme à rechercher:");
+    let query = read_line()?;
+
+    if query.is_empty() {
+        println!("Entrez au moins un caractère pour lancer la recherche.");
+        return Ok(());
+    }
+
+    println!("Mode de recherche:");
+    println!("1 - Texte (par défaut)");
+    println!("2 - Expression régulière (. ^ $ * + ? | ( ) [a-z] \\d \\w \\s; pas de {{n,m}}, \\b ni \\1)");
+    let regex = read_line()? == "2";
+    let case_sensitive = confirm("Respecter les majuscules?");
+
+    match search_all(&query, SearchOptions { regex, case_sensitive }) {
+        Ok(results) => print_search_results(&query, &results),
+        Err(e) => println!("Expression régulière refusée: {}", e),
+    }
+    Ok(())
+}
+
+fn print_search_results(query: &str, results: &[(String, TodoItem)]) {
+    if results.is_empty() {
+        println!("Aucune tâche ne correspond à '{}'.", query);
+        return;
+    }
+
+    println!("{} résultat(s) pour '{}':", results.len(), query);
+    for (list_name, item) in results {
+        println!("{} → [{}] {}", list_name, item.id, item.title);
+    }
+}
+
+// nombre d'opérations affichées, les plus récentes
+const HISTORY_DISPLAY_LIMIT: usize = 20;
+
+fn afficher_historique() -> io::Result<()> {
+    let Some(list_name) = choose_list("consulter")? else {
+        return Ok(());
+    };
+    let history = load_history(&list_name);
+    if history.is_empty() {
+        println!("Aucune opération enregistrée pour '{}'.", list_name);
+        return Ok(());
+    }
+
+    let start = history.len().saturating_sub(HISTORY_DISPLAY_LIMIT);
+    let title = format!("Historique de {} ({} opération(s), {} dernière(s))", list_name, history.len(), history.len() - start);
+    println!("{}", section_header(&title));
+    for entry in &history[start..] {
+        println!("{} - {} [{}] {}", format_datetime(entry.timestamp), entry.action.to_string(), entry.item_id, entry.title);
+    }
+    Ok(())
+}
+
+fn graphique_completions() -> io::Result<()> {
+    let Some(list_name) = choose_list("consulter")? else {
+        return Ok(());
+    };
+    let todo_list = match load_todo_list(&list_name) {
+        Ok(todo_list) => todo_list,
+        Err(e) => {
+            println!("Erreur lors du chargement: {}", e);
+            return Ok(());
+        }
+    };
+
+    println!("Regrouper par:");
+    println!("1 - Jour");
+    println!("2 - Semaine");
+    let bucket = loop {
+        match read_number()? {
+            1 => break Bucket::Day,
+            2 => break Bucket::Week,
+            _ => println!("Choix invalide. Entrez 1 ou 2."),
+        }
+    };
+
+    let histogram = completion_histogram(&todo_list, bucket, config().language(), config().date_format());
+    if histogram.is_empty() {
+        println!("Aucune tâche terminée dans '{}'.", todo_list.name);
+        return Ok(());
+    }
+
+    // la barre la plus longue occupe la place laissée par le libellé et le nombre
+    let label_width = histogram.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+    let max_count = histogram.iter().map(|(_, count)| *count).max().unwrap_or(1);
+    let bar_width = terminal_width().saturating_sub(label_width + 10).max(10);
+    println!("{}", section_header(&format!("{} - tâches terminées", todo_list.name)));
+    for (label, count) in &histogram {
+        let bar = (count * bar_width).div_ceil(max_count);
+        println!("{:<width$} | {} {}", label, "#".repeat(bar), count, width = label_width);
+    }
+    Ok(())
+}
+
+fn tableau_de_bord() -> io::Result<()> {
+    let summaries = dashboard_summary();
+    if summaries.is_empty() {
+        println!("Aucune liste sauvegardée.");
+        return Ok(());
+    }
+
+    println!("{}", section_header("Tableau de bord"));
+    for summary in &summaries {
+        let next_due = match summary.next_due {
+            Some(due_date) => format!(" - prochaine échéance {}", format_date(due_date)),
+            None => String::new(),
+        };
+        println!(
+            "{} : {} élément(s), {} terminé(s), {} restant(s){}",
+            summary.name, summary.total, summary.completed, summary.pending(), next_due
+        );
+    }
+    Ok(())
+}
+
+// $VISUAL ou $EDITOR (avec ses éventuels arguments, ex: "code --wait"), sinon vi
+fn editor_command() -> Vec<String> {
+    let editor = env::var("VISUAL").or_else(|_| env::var("EDITOR")).unwrap_or_default();
+    let parts: Vec<String> = editor.split_whitespace().map(str::to_string).collect();
+    if parts.is_empty() { vec!["vi".to_string()] } else { parts }
+}
+
+// contenu texte du presse-papiers, lu avec le premier outil disponible
+// (Wayland, X11, macOS); None sans session graphique ou sans aucun de ces outils
+fn clipboard_text() -> Option<String> {
+    const READERS: [(&str, &[&str]); 4] = [
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-o"]),
+        ("xsel", &["--clipboard", "--output"]),
+        ("pbpaste", &[]),
+    ];
+    READERS.iter().find_map(|(program, args)| {
+        let output = Command::new(program).args(*args).stdin(Stdio::null()).stderr(Stdio::null()).output().ok()?;
+        let text = String::from_utf8(output.stdout).ok()?;
+        (output.status.success() && !text.trim().is_empty()).then_some(text)
+    })
+}
+
+// ouvre le fichier de la liste dans l'éditeur puis le relit; si le résultat est invalide,
+// la version d'avant est gardée en .bak et peut être remise en place
+fn editer_json() -> io::Result<()> {
+    let Some(list_name) = choose_list("éditer")? else {
+        return Ok(());
+    };
+    let Some(_lock) = acquire_lock(&list_name)? else {
+        return Ok(());
+    };
+    if config().storage == StorageBackend::Combined {
+        return editer_json_extrait(&list_name);
+    }
+    let path = storage_path(&list_name);
+    let original = fs::read_to_string(&path)?;
+
+    if !run_editor(&path) {
+        return Ok(());
+    }
+
+    if fs::read_to_string(&path).is_ok_and(|edited| edited == original) {
+        println!("Aucune modification.");
+        return Ok(());
+    }
+    match load_todo_list(&list_name) {
+        Ok(todo_list) => {
+            println!("Liste '{}' rechargée: {} élément(s), {} archivé(s).", todo_list.name, todo_list.items.len(), todo_list.archived.len());
+        }
+        Err(e) => {
+            println!("Le fichier modifié est invalide: {}", e);
+            let backup_path = path.with_extension(format!("json.{}.bak", Utc::now().format("%Y%m%d%H%M%S")));
+            fs::write(&backup_path, &original)?;
+            println!("La version d'avant la modification est sauvegardée dans '{}'.", backup_path.display());
+            if confirm("Remettre la version d'avant la modification?") {
+                fs::write(&path, &original)?;
+                println!("Version précédente restaurée.");
+            } else {
+                println!("Le fichier modifié est conservé; corrigez-le avant de rouvrir la liste.");
+            }
+        }
+    }
+    Ok(())
+}
+
+// todos.json contient toutes les listes mais seule celle-ci est verrouillée: on l'édite
+// dans un fichier à part, puis elle est réenregistrée seule, comme après une modification
+fn editer_json_extrait(list_name: &str) -> io::Result<()> {
+    let todo_list = match load_todo_list(list_name) {
+        Ok(todo_list) => todo_list,
+        Err(e) => {
+            println!("Erreur lors du chargement de '{}': {}", list_name, e);
+            return Ok(());
+        }
+    };
+    let path = env::temp_dir().join(format!("todocli_{}_{}.json", sanitize_name(list_name), process::id()));
+    let original = serde_json::to_string_pretty(&todo_list)?;
+    fs::write(&path, &original)?;
+
+    if !run_editor(&path) {
+        fs::remove_file(&path)?;
+        return Ok(());
+    }
+
+    let edited = fs::read_to_string(&path)?;
+    if edited == original {
+        println!("Aucune modification.");
+        fs::remove_file(&path)?;
+        return Ok(());
+    }
+    let parsed = serde_json::from_str(&edited).and_then(migrate);
+    match parsed {
+        Ok(mut edited_list) => {
+            // la clé dans todos.json vient du nom: le renommer créerait une deuxième liste
+            edited_list.name = todo_list.name;
+            let mut todo_list = edited_list;
+            match save_list(&mut todo_list, config().verbosity) {
+                Ok(()) => {
+                    println!("Liste '{}' rechargée: {} élément(s), {} archivé(s).", todo_list.name, todo_list.items.len(), todo_list.archived.len());
+                    fs::remove_file(&path)?;
+                }
+                Err(e) => {
+                    println!("Erreur lors de la sauvegarde: {}", e);
+                    println!("Vos modifications sont conservées dans '{}'.", path.display());
+                }
+            }
+        }
+        Err(e) => {
+            println!("Le fichier modifié est invalide: {}", e);
+            println!("La liste n'a pas été modifiée; vos modifications sont conservées dans '{}'.", path.display());
+        }
+    }
+    Ok(())
+}
+
+// false si l'éditeur n'a pas pu être lancé
+fn run_editor(path: &std::path::Path) -> bool {
+    let editor = editor_command();
+    match Command::new(&editor[0]).args(&editor[1..]).arg(path).status() {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            println!("L'éditeur s'est terminé en erreur ({}).", status);
+            true
+        }
+        Err(e) => {
+            println!("Impossible de lancer l'éditeur '{}': {} (définissez la variable EDITOR).", editor[0], e);
+            false
+        }
+    }
+}
+
+fn statistiques_globales() -> io::Result<()> {
+    let stats = global_stats();
+    if stats.lists.is_empty() {
+        println!("Aucune liste sauvegardée.");
+        return Ok(());
+    }
+
+    println!("{}", section_header("Statistiques globales"));
+    println!("Listes: {}", stats.lists.len());
+    println!("Éléments: {} dont {} terminé(s)", stats.total, stats.completed);
+    println!("Taux de complétion: {:.0}% {}", stats.completion_rate(), progress_bar(stats.completion_rate()));
+    if let Some(busiest) = stats.busiest() {
+        println!("Liste la plus chargée: {} ({} restant(s))", busiest.name, busiest.pending());
+    }
+    if let Some(lowest) = stats.lowest_completion() {
+        println!("Liste la moins avancée: {} ({:.0}%)", lowest.name, lowest.completion_rate());
+    }
+
+    println!();
+    for list in &stats.lists {
+        println!("{} : {}/{} terminé(s) ({:.0}%)", list.name, list.completed, list.total, list.completion_rate());
+    }
+    Ok(())
+}
+
+fn taches_en_retard() -> io::Result<()> {
+    let results = overdue_all();
+    if results.is_empty() {
+        println!("Aucune tâche en retard!");
+        return Ok(());
+    }
+
+    // regroupé par liste, en gardant l'ordre du retard le plus important
+    let mut groups: Vec<(&str, Vec<&TodoItem>)> = Vec::new();
+    for (list_name, item) in &results {
+        match groups.iter_mut().find(|(name, _)| name == list_name) {
+            Some((_, items)) => items.push(item),
+            None => groups.push((list_name, vec![item])),
+        }
+    }
+
+    println!("{} tâche(s) en retard:", results.len());
+    let today = Local::now().date_naive();
+    for (list_name, items) in groups {
+        println!("\n{}", section_header(list_name));
+        for item in items {
+            let due_date = item.due_date.expect("une tâche en retard a une échéance");
+            let days = (today - due_date).num_days();
+            println!("[{}] {} - échéance {} ({} jour(s) de retard)", item.id, item.title, format_date(due_date), days);
+        }
+    }
+    Ok(())
+}
+
+fn plus_vieille_tache() -> io::Result<()> {
+    match oldest_incomplete_all() {
+        Some((list_name, item)) => {
+            println!("{}", tr_args("oldest.found", &[&item.id, &item.title, &list_name, &humanize_age(item.created_at)]));
+        }
+        None => println!("{}", tr("oldest.none")),
+    }
+    Ok(())
+}
+
+fn supprimer_liste() -> io::Result<()> {
+    let Some(list_name) = choose_list("supprimer")? else {
+        return Ok(());
+    };
+    confirm_and_delete(&list_name)
+}
+
+// la liste part dans la corbeille, d'où elle peut être restaurée
+fn confirm_and_delete(list_name: &str) -> io::Result<()> {
+    let Some(_lock) = acquire_lock(list_name)? else {
+        return Ok(());
+    };
+    // le contenu est rappelé avant de confirmer; une liste vide se supprime comme les autres
+    if let Ok(todo_list) = load_todo_list(list_name) {
+        match todo_list.items.len() {
+            0 => println!("{}", tr("common.list_empty")),
+            count => println!("La liste '{}' contient {} élément(s).", list_name, count),
+        }
     }
-    
-    println!("Choisissez le numéro de la liste à supprimer:");
-    let mut choice = String::new();
-    io::stdin().read_line(&mut choice).expect("Erreur de lecture");
-    let choice: usize = choice.trim().parse().expect("Veuillez entrer un nombre");
-    
-    if choice > 0 && choice <= lists.len() {
-        let list_name = &lists[choice - 1];
-        let filename = format!("{}.json", list_name.replace(" ", "_").to_lowercase());
-        
-        println!("Êtes-vous sûr de vouloir supprimer la liste '{}'? (oui/non)", list_name);
-        let mut confirm = String::new();
-        io::stdin().read_line(&mut confirm).expect("Erreur de lecture");
-        
-        if confirm.trim().to_lowercase() == "oui" {
-            if let Err(e) = fs::remove_file(filename) {
-                println!("Erreur lors de la suppression: {}", e);
-            } else {
-                println!("Liste '{}' supprimée avec succès!", list_name);
+    if confirm(&format!("Êtes-vous sûr de vouloir supprimer la liste '{}'?", list_name)) {
+        match trash_list(list_name, false) {
+            Ok(_) => println!("Liste '{}' déplacée dans la corbeille.", list_name),
+            Err(e) => println!("Erreur lors de la suppression: {}", e),
+        }
+    } else {
+        println!("Suppression annulée.");
+    }
+    Ok(())
+}
+
+fn corbeille() -> io::Result<()> {
+    loop {
+        let entries = list_trash();
+        if entries.is_empty() {
+            println!("La corbeille est vide.");
+            return Ok(());
+        }
+
+        println!("\nListes dans la corbeille:");
+        for (i, entry) in entries.iter().enumerate() {
+            println!("{}. {}", i + 1, entry);
+        }
+        println!("\n1 - Restaurer une liste");
+        println!("2 - Vider la corbeille");
+        println!("3 - Retour");
+
+        match read_number()? {
+            1 => {
+                println!("Choisissez le numéro de la liste à restaurer:");
+                let choice = read_number()? as usize;
+                let Some(entry) = choice.checked_sub(1).and_then(|index| entries.get(index)) else {
+                    println!("{}", tr("common.invalid_choice"));
+                    continue;
+                };
+                match restore_from_trash(entry) {
+                    Ok(path) => println!("Liste restaurée dans {}.", path.display()),
+                    Err(e) => println!("Erreur lors de la restauration: {}", e),
+                }
+            }
+            2 => {
+                if !confirm(&format!("Supprimer définitivement {} liste(s)?", entries.len())) {
+                    println!("Corbeille conservée.");
+                    continue;
+                }
+                match empty_trash() {
+                    Ok(count) => println!("{} liste(s) supprimée(s) définitivement.", count),
+                    Err(e) => println!("Erreur lors de la suppression: {}", e),
+                }
             }
-        } else {
-            println!("Suppression annulée.");
+            3 => return Ok(()),
+            _ => println!("{}", tr("common.invalid_choice")),
+        }
+    }
+}
+
+fn rechercher_doublons() -> io::Result<()> {
+    let groups = find_duplicate_lists();
+    if groups.is_empty() {
+        println!("Aucune liste en double.");
+        return Ok(());
+    }
+
+    println!("{} groupe(s) de listes identiques:", groups.len());
+    for group in groups {
+        println!("\nListes au contenu identique:");
+        for (i, list_name) in group.iter().enumerate() {
+            println!("{} - {}", i + 1, list_name);
+        }
+        println!("Numéro de la liste à supprimer (Entrée pour toutes les garder):");
+        let input = read_line()?;
+        if input.is_empty() {
+            continue;
+        }
+        match input.parse::<usize>() {
+            Ok(choice) if choice > 0 && choice <= group.len() => confirm_and_delete(&group[choice - 1])?,
+            _ => println!("{}", tr("common.invalid_choice")),
         }
+    }
+    Ok(())
+}
+
+fn dupliquer_liste() -> io::Result<()> {
+    let Some(source) = choose_list("dupliquer")? else {
+        return Ok(());
+    };
+
+    println!("Entrez le nom de la nouvelle liste:");
+    let name = read_line()?;
+
+    if sanitize_name(&name).is_empty() {
+        println!("Le nom doit contenir au moins une lettre ou un chiffre!");
+        return Ok(());
+    }
+
+    let Some(_lock) = lock_for_overwrite(&name, "Duplication annulée.")? else {
+        return Ok(());
+    };
+
+    match duplicate_list(&source, &name) {
+        Ok(()) => println!("Liste '{}' sauvegardée avec succès!", name),
+        Err(e) => println!("Erreur lors de la duplication: {}", e),
+    }
+    Ok(())
+}
+
+fn deplacer_tache() -> io::Result<()> {
+    println!("Liste d'origine:");
+    let Some(source) = choose_list("consulter")? else {
+        return Ok(());
+    };
+    let Some(_source_lock) = acquire_lock(&source)? else {
+        return Ok(());
+    };
+    let Some(todo_list) = open_todo_list(&source)? else {
+        return Ok(());
+    };
+    if !has_items(&todo_list) {
+        return Ok(());
+    }
+
+    todo_list.display();
+    println!("Entrez l'ID de l'élément à déplacer:");
+    let id = read_number()?;
+
+    println!("Liste de destination:");
+    let Some(destination) = choose_list("compléter")? else {
+        return Ok(());
+    };
+    // même liste: transfer_item refuse, inutile de la verrouiller une seconde fois
+    let _destination_lock = if sanitize_name(&destination) == sanitize_name(&source) {
+        None
     } else {
-        println!("Choix invalide.");
+        let Some(lock) = acquire_lock(&destination)? else {
+            return Ok(());
+        };
+        Some(lock)
+    };
+
+    match transfer_item(&source, id, &destination) {
+        Ok(compacted) => {
+            println!("Élément déplacé de '{}' vers '{}'!", todo_list.name, destination);
+            print_compaction(compacted, &destination, config().verbosity);
+        }
+        Err(e) => println!("Erreur lors du déplacement: {}", e),
+    }
+    Ok(())
+}
+
+fn exporter_markdown() -> io::Result<()> {
+    let Some(list_name) = choose_list("exporter")? else {
+        return Ok(());
+    };
+
+    let Some(todo_list) = open_todo_list(&list_name)? else {
+        return Ok(());
+    };
+
+    let filename = format!("{}.md", sanitize_name(&todo_list.name));
+    match fs::write(&filename, export_markdown(&todo_list, config().language(), config().date_format())) {
+        Ok(()) => println!("Liste '{}' exportée dans '{}'!", todo_list.name, filename),
+        Err(e) => println!("Erreur lors de l'export: {}", e),
+    }
+    Ok(())
+}
+
+fn exporter_csv() -> io::Result<()> {
+    let Some(list_name) = choose_list("exporter")? else {
+        return Ok(());
+    };
+
+    let Some(todo_list) = open_todo_list(&list_name)? else {
+        return Ok(());
+    };
+
+    let filename = format!("{}.csv", sanitize_name(&todo_list.name));
+    match fs::write(&filename, export_csv(&todo_list)) {
+        Ok(()) => println!("Liste '{}' exportée dans '{}'!", todo_list.name, filename),
+        Err(e) => println!("Erreur lors de l'export: {}", e),
+    }
+    Ok(())
+}
+
+fn exporter_ics() -> io::Result<()> {
+    let Some(list_name) = choose_list("exporter")? else {
+        return Ok(());
+    };
+
+    let Some(todo_list) = open_todo_list(&list_name)? else {
+        return Ok(());
+    };
+
+    let filename = format!("{}.ics", sanitize_name(&todo_list.name));
+    match fs::write(&filename, export_ics(&todo_list)) {
+        Ok(()) => println!("Liste '{}' exportée dans '{}'!", todo_list.name, filename),
+        Err(e) => println!("Erreur lors de l'export: {}", e),
+    }
+    Ok(())
+}
+
+fn sauvegarder_tout() -> io::Result<()> {
+    let default_dest = format!("todocli_backup_{}.json", Local::now().format("%Y%m%d_%H%M%S"));
+    println!("Entrez le chemin du fichier de sauvegarde (Entrée pour '{}'):", default_dest);
+    let dest = read_line()?;
+    let dest = if dest.is_empty() { default_dest } else { dest };
+
+    match backup_all(&dest) {
+        Ok(()) => println!("Toutes les listes ont été sauvegardées dans '{}'!", dest),
+        Err(e) => println!("Erreur lors de la sauvegarde: {}", e),
+    }
+    Ok(())
+}
+
+fn restaurer_sauvegarde() -> io::Result<()> {
+    println!("Entrez le chemin du fichier de sauvegarde à restaurer:");
+    let src = read_line()?;
+
+    if !confirm("Les listes existantes portant le même nom seront remplacées. Continuer?") {
+        println!("Restauration annulée.");
+        return Ok(());
+    }
+    // le fichier est entièrement lu avant de verrouiller et d'écrire quoi que ce soit
+    let lists = match read_backup(&src) {
+        Ok(lists) => lists,
+        Err(e) => {
+            println!("Erreur lors de la restauration de '{}': {}", src, e);
+            return Ok(());
+        }
+    };
+    let names: Vec<String> = lists.iter().map(|todo_list| todo_list.name.clone()).collect();
+    let Some(_locks) = acquire_all_locks(&names)? else {
+        return Ok(());
+    };
+
+    let mut count = 0;
+    for mut todo_list in lists {
+        if let Err(e) = save_todo_list(&mut todo_list) {
+            println!("Erreur lors de la restauration de '{}': {}", todo_list.name, e);
+            break;
+        }
+        count += 1;
+    }
+    println!("{} liste(s) restaurée(s)!", count);
+    Ok(())
+}
+
+fn importer_csv() -> io::Result<()> {
+    println!("Entrez le chemin du fichier CSV à importer:");
+    let path = read_line()?;
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            println!("Erreur lors de la lecture de '{}': {}", path, e);
+            return Ok(());
+        }
+    };
+
+    println!("Entrez le nom de la nouvelle liste:");
+    let name = read_line()?;
+
+    if sanitize_name(&name).is_empty() {
+        println!("Le nom doit contenir au moins une lettre ou un chiffre!");
+        return Ok(());
+    }
+
+    let Some(_lock) = lock_for_overwrite(&name, "Import annulé.")? else {
+        return Ok(());
+    };
+
+    match import_csv(&name, &content) {
+        Ok(mut todo_list) => {
+            println!("{} élément(s) importé(s).", todo_list.items.len());
+            if let Err(e) = save_list(&mut todo_list, config().verbosity) {
+                println!("Erreur lors de la sauvegarde: {}", e);
+            }
+        }
+        Err(e) => println!("Erreur lors de l'import: {}", e),
+    }
+    Ok(())
+}
+
+fn capture_rapide() -> io::Result<()> {
+    let list_name = config().default_list_name();
+    println!("Entrez le titre de la tâche à ajouter à '{}':", list_name);
+    let title = read_line()?;
+    if title.is_empty() {
+        println!("Le titre ne peut pas être vide!");
+        return Ok(());
+    }
+    let Some(_lock) = acquire_lock(&list_name)? else {
+        return Ok(());
+    };
+
+    match load_or_create_todo_list(&list_name) {
+        Ok(mut todo_list) => {
+            todo_list.add_item(title, None);
+            if let Err(e) = save_list(&mut todo_list, config().verbosity) {
+                println!("Erreur lors de la sauvegarde: {}", e);
+            }
+        }
+        Err(e) => println!("Erreur lors du chargement de '{}': {}", list_name, e),
+    }
+    Ok(())
+}
+
+fn importer_texte() -> io::Result<()> {
+    println!("Entrez le chemin du fichier texte à importer (une tâche par ligne, '[x]' pour une tâche terminée):");
+    let path = read_line()?;
+
+    println!("Entrez le nom de la nouvelle liste:");
+    let name = read_line()?;
+
+    if sanitize_name(&name).is_empty() {
+        println!("Le nom doit contenir au moins une lettre ou un chiffre!");
+        return Ok(());
+    }
+
+    let Some(_lock) = lock_for_overwrite(&name, "Import annulé.")? else {
+        return Ok(());
+    };
+
+    match import_text(&name, &path) {
+        Ok(mut todo_list) => {
+            println!("{} élément(s) importé(s).", todo_list.items.len());
+            if let Err(e) = save_list(&mut todo_list, config().verbosity) {
+                println!("Erreur lors de la sauvegarde: {}", e);
+            }
+        }
+        Err(e) => println!("Erreur lors de l'import de '{}': {}", path, e),
+    }
+    Ok(())
+}
+
+// une tâche par ligne du presse-papiers, ajoutée comme avec "Ajouter en lot"
+fn importer_presse_papiers() -> io::Result<()> {
+    let Some(list_name) = choose_list("compléter")? else {
+        return Ok(());
+    };
+    let Some(_lock) = acquire_lock(&list_name)? else {
+        return Ok(());
+    };
+    let Some(mut todo_list) = open_todo_list(&list_name)? else {
+        return Ok(());
+    };
+
+    let lines = match clipboard_text() {
+        Some(text) => {
+            let lines: Vec<String> = text.lines().map(str::trim).filter(|line| !line.is_empty()).map(str::to_string).collect();
+            println!("{} tâche(s) dans le presse-papiers:", lines.len());
+            for line in &lines {
+                println!("  {}", line);
+            }
+            if !confirm(&format!("Les ajouter à '{}'?", todo_list.name)) {
+                println!("Import annulé.");
+                return Ok(());
+            }
+            lines
+        }
+        None => {
+            println!("Presse-papiers inaccessible (installez wl-paste, xclip ou xsel).");
+            read_pasted_lines()?
+        }
+    };
+
+    let count = todo_list.add_items_bulk(lines.iter().map(String::as_str));
+    println!("{} élément(s) ajouté(s).", count);
+    if count > 0
+        && let Err(e) = save_list(&mut todo_list, config().verbosity)
+    {
+        println!("Erreur lors de la sauvegarde: {}", e);
     }
+    Ok(())
 }
 
-fn main() {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // avec des arguments on passe en mode non interactif, sinon on affiche le menu
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let options = CliOptions::extract(&mut args);
+    if !args.is_empty() {
+        if let Err(e) = run_cli(&args, options) {
+            process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
+    if options.json {
+        eprintln!("L'option --json est ignorée en mode interactif.");
+    }
+    if options.dry_run {
+        eprintln!("L'option --dry-run est ignorée en mode interactif.");
+    }
+    if options.verbosity != config().verbosity {
+        eprintln!("Les options -q et -v sont ignorées en mode interactif (voir \"verbosity\" dans config.json).");
+    }
+
+    display_due_soon_banner();
+    let mut pending_summary = PendingSummary::default();
+
     loop {
+        if let Some(summary) = pending_summary.refresh() {
+            println!("{}\n", summary);
+        }
         // affichage du menu
-        let actions = ["Créer une nouvelle liste", "Mettre à jour une liste existante", "Supprimer une liste existante", "Quitter"];
+        let actions = [
+            "main.create_list",
+            "main.update_list",
+            "main.delete_list",
+            "main.search",
+            "main.export_markdown",
+            "main.export_csv",
+            "main.import_csv",
+            "main.duplicate_list",
+            "main.resume_last",
+            "main.backup_all",
+            "main.restore_backup",
+            "main.move_task",
+            "main.overdue",
+            "main.duplicates",
+            "main.dashboard",
+            "main.history",
+            "main.completion_chart",
+            "main.trash",
+            "main.import_text",
+            "main.quick_capture",
+            "main.global_stats",
+            "main.edit_json",
+            "main.import_clipboard",
+            "main.oldest_task",
+            "main.export_ics",
+            "main.quit",
+        ];
         display_actions(&actions);
 
-        let mut input = String::new();
-
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Erreur de lecture");
-
         // convertis l'entrée en nombres
-        let choix: u32 = input.trim().parse().expect("Veuillez entrer un nombre");
+        let choix = match read_menu_selection(actions.len() as u32) {
+            Ok(Some(choix)) => choix,
+            Ok(None) => {
+                println!("{}", tr("common.invalid_number"));
+                continue;
+            }
+            Err(e) if is_end_of_input(&e) => break,
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                println!("{}", tr_args("common.read_error", &[&e]));
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
 
         // utilisation de match pour exécuter une action selon le choix
-        match choix {
+        let result = match choix {
             1 => creer_liste(),
             2 => mettre_a_jour_liste(),
             3 => supprimer_liste(),
-            4 => {
-                println!("Au revoir!");
-                break;
+            4 => rechercher_tache(),
+            5 => exporter_markdown(),
+            6 => exporter_csv(),
+            7 => importer_csv(),
+            8 => dupliquer_liste(),
+            9 => reprendre_derniere_liste(),
+            10 => sauvegarder_tout(),
+            11 => restaurer_sauvegarde(),
+            12 => deplacer_tache(),
+            13 => taches_en_retard(),
+            14 => rechercher_doublons(),
+            15 => tableau_de_bord(),
+            16 => afficher_historique(),
+            17 => graphique_completions(),
+            18 => corbeille(),
+            19 => importer_texte(),
+            20 => capture_rapide(),
+            21 => statistiques_globales(),
+            22 => editer_json(),
+            23 => importer_presse_papiers(),
+            24 => plus_vieille_tache(),
+            25 => exporter_ics(),
+            26 => break,
+            _ => {
+                println!("{}", tr("common.invalid_choice"));
+                Ok(())
             }
-            _ => println!("Choix invalide."),
+        };
+
+        // une erreur de lecture interrompt l'action en cours mais pas la session
+        match result {
+            Err(e) if is_end_of_input(&e) => break,
+            Err(e) => println!("{}", tr_args("common.read_error", &[&e])),
+            Ok(()) => {}
         }
         
         println!("\n\n");
     }
+
+    println!("{}", tr("common.goodbye"));
+    Ok(())
+}
+
+// ligne affichée au-dessus du menu principal; les listes ne sont relues que si un fichier a changé
+#[derive(Default)]
+struct PendingSummary {
+    fingerprint: Option<(usize, Option<SystemTime>)>,
+    text: Option<String>,
+}
+
+impl PendingSummary {
+    fn refresh(&mut self) -> Option<&str> {
+        let fingerprint = data_dir_fingerprint();
+        if self.fingerprint != Some(fingerprint) {
+            self.fingerprint = Some(fingerprint);
+            self.text = Self::compute();
+        }
+        self.text.as_deref()
+    }
+
+    // rien tant qu'aucune liste n'est sauvegardée
+    fn compute() -> Option<String> {
+        let stats = global_stats();
+        if stats.lists.is_empty() {
+            return None;
+        }
+        let pending = stats.total - stats.completed;
+        if pending == 0 {
+            return Some("Vous n'avez aucune tâche en attente.".to_string());
+        }
+        let lists = stats.lists.iter().filter(|list| list.pending() > 0).count();
+        Some(format!("Vous avez {} tâche(s) en attente réparties sur {} liste(s).", pending, lists))
+    }
 }
 
 // fonction pour afficher le menu
+// `actions`: clés du catalogue (voir tr)
 fn display_actions(actions: &[&str]) {
-    println!("Bonjour! Choisissez une action parmi ce que vous souhaitez faire :");
+    println!("{}", tr("main.greeting"));
     for (i, action) in actions.iter().enumerate() {
-        println!("{} - {}", i + 1, action);
+        println!("{} - {}", i + 1, tr(action));
+    }
+}
+
+// échecs du mode ligne de commande, chacun avec son code de sortie
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CliError {
+    Usage,
+    ListNotFound,
+    ItemNotFound,
+    Save,
+    Input,
+    PendingSubtasks,
+    Locked,
+}
+
+impl CliError {
+    fn exit_code(self) -> i32 {
+        match self {
+            CliError::Usage => 1,
+            CliError::ListNotFound => 2,
+            CliError::ItemNotFound => 3,
+            CliError::Save => 4,
+            CliError::Input => 5,
+            CliError::PendingSubtasks => 6,
+            CliError::Locked => 7,
+        }
+    }
+}
+
+// mode non interactif, pour les scripts et les alias
+// options globales, valables quelle que soit leur place dans la ligne de commande
+// avec `json`, list et search écrivent du JSON sur la sortie standard (pour jq par exemple)
+// avec `dry_run`, delete, clear et archive décrivent leur effet sans toucher aux fichiers
+// `verbosity` vient de config.json, sauf si -q/--quiet ou -v/--verbose est passé (le dernier l'emporte)
+#[derive(Debug, Clone, Copy)]
+struct CliOptions {
+    json: bool,
+    dry_run: bool,
+    verbosity: Verbosity,
+}
+
+impl CliOptions {
+    // retire les options de `args`, il ne reste que la commande et ses arguments
+    fn extract(args: &mut Vec<String>) -> CliOptions {
+        let mut options = CliOptions { json: false, dry_run: false, verbosity: config().verbosity };
+        args.retain(|arg| {
+            match arg.as_str() {
+                "--json" => options.json = true,
+                "--dry-run" => options.dry_run = true,
+                "-q" | "--quiet" => options.verbosity = Verbosity::Quiet,
+                "-v" | "--verbose" => options.verbosity = Verbosity::Verbose,
+                _ => return true,
+            }
+            false
+        });
+        options
+    }
+}
+
+fn run_cli(args: &[String], options: CliOptions) -> Result<(), CliError> {
+    let CliOptions { json, dry_run, verbosity } = options;
+    if json && !matches!(args[0].as_str(), "list" | "search") {
+        eprintln!("L'option --json est ignorée pour la commande '{}'.", args[0]);
+    }
+    if dry_run && !matches!(args[0].as_str(), "delete" | "clear" | "archive") {
+        eprintln!("L'option --dry-run est ignorée pour la commande '{}'.", args[0]);
+    }
+    match (args[0].as_str(), &args[1..]) {
+        // sans nom de liste, l'élément va dans la liste par défaut
+        ("add", [title]) => {
+            let list_name = config().default_list_name();
+            let _lock = lock_cli_list(&list_name)?;
+            let mut todo_list = load_or_create_todo_list(&list_name).map_err(|e| {
+                eprintln!("Erreur lors du chargement de '{}': {}", list_name, e);
+                CliError::ListNotFound
+            })?;
+            todo_list.add_item(title.clone(), None);
+            if verbosity.shows_success() {
+                println!("Élément ajouté à '{}'!", todo_list.name);
+            }
+            save_cli_list(&mut todo_list, false, verbosity)
+        }
+        ("add", [list_name, title, rest @ ..]) => {
+            let (_lock, mut todo_list) = load_locked_cli_list(list_name)?;
+            todo_list.add_item(title.clone(), rest.first().cloned());
+            if verbosity.shows_success() {
+                println!("Élément ajouté!");
+            }
+            save_cli_list(&mut todo_list, false, verbosity)
+        }
+        ("done", [list_name, item, flags @ ..]) if flags.iter().all(|flag| flag == "--force") => {
+            let (_lock, mut todo_list) = load_locked_cli_list(list_name)?;
+            let rule = if flags.is_empty() { config().subtask_rule() } else { SubtaskRule::Ignore };
+            // un argument non numérique désigne l'élément par son titre
+            let toggled = match item.parse::<u32>() {
+                Ok(id) => todo_list.toggle_item(id, rule),
+                Err(_) => todo_list.toggle_by_title(item, rule),
+            };
+            match toggled {
+                Ok(()) => {}
+                Err(ToggleError::NotFound) => {
+                    eprintln!("{}", tr("common.item_not_found"));
+                    return Err(CliError::ItemNotFound);
+                }
+                Err(e @ ToggleError::PendingSubtasks(_)) => {
+                    eprintln!("Erreur: {}; ajoutez --force pour terminer quand même.", e);
+                    return Err(CliError::PendingSubtasks);
+                }
+                Err(e) => {
+                    eprintln!("Erreur: {}; précisez l'ID.", e);
+                    return Err(CliError::ItemNotFound);
+                }
+            }
+            if verbosity.shows_success() {
+                println!("Statut modifié!");
+            }
+            save_cli_list(&mut todo_list, false, verbosity)
+        }
+        // pas de confirmation ici: la liste reste récupérable depuis la corbeille
+        ("delete", [list_name]) => {
+            let list_name = resolve_list_name(list_name).ok_or(CliError::ListNotFound)?;
+            let _lock = lock_cli_list(&list_name)?;
+            match trash_list(&list_name, dry_run) {
+                Ok(entry) if dry_run => {
+                    println!("[simulation] La liste '{}' serait déplacée dans la corbeille ({}).", list_name, entry);
+                    Ok(())
+                }
+                Ok(entry) => {
+                    if verbosity.shows_success() {
+                        println!("Liste '{}' déplacée dans la corbeille.", list_name);
+                    }
+                    if verbosity.shows_details() {
+                        println!("Fichier: {}", trash_dir().join(format!("{}.json", entry)).display());
+                    }
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("Erreur lors de la suppression: {}", e);
+                    Err(CliError::Save)
+                }
+            }
+        }
+        ("clear" | "archive", [list_name]) => {
+            let (_lock, mut todo_list) = load_locked_cli_list(list_name)?;
+            let affected: Vec<String> = todo_list
+                .items
+                .iter()
+                .filter(|item| item.status.is_done())
+                .map(|item| format!("  [{}] {}", item.id, item.title))
+                .collect();
+            let (count, verb) = if args[0] == "clear" {
+                (todo_list.clear_completed(), "supprimé")
+            } else {
+                (todo_list.archive_completed(), "archivé")
+            };
+            if dry_run {
+                println!("[simulation] {} élément(s) terminé(s) seraient {}s:", count, verb);
+                for line in &affected {
+                    println!("{}", line);
+                }
+            } else if verbosity.shows_success() {
+                println!("{} élément(s) terminé(s) {}(s).", count, verb);
+                if verbosity.shows_details() {
+                    for line in &affected {
+                        println!("{}", line);
+                    }
+                }
+            }
+            if count == 0 {
+                return Ok(());
+            }
+            save_cli_list(&mut todo_list, dry_run, verbosity)
+        }
+        // numéro tel qu'affiché par le menu de choix des listes, puis édition interactive
+        ("open", [index]) => {
+            let Ok(index) = index.parse::<usize>() else {
+                eprintln!("{}", tr("common.invalid_number"));
+                return Err(CliError::Usage);
+            };
+            let lists = list_saved_todo_lists();
+            let Some(list_name) = index.checked_sub(1).and_then(|index| lists.get(index)) else {
+                eprintln!("Aucune liste n°{} ({} liste(s) sauvegardée(s)).", index, lists.len());
+                return Err(CliError::ListNotFound);
+            };
+            match editer_liste(list_name) {
+                Ok(()) => Ok(()),
+                Err(e) if is_end_of_input(&e) => Ok(()),
+                Err(e) => {
+                    eprintln!("{}", tr_args("common.read_error", &[&e]));
+                    Err(CliError::Input)
+                }
+            }
+        }
+        ("list", [list_name]) => {
+            let todo_list = load_cli_list(list_name)?;
+            if json {
+                print_json(&todo_list);
+            } else {
+                todo_list.display();
+            }
+            Ok(())
+        }
+        // une ligne "id<TAB>état<TAB>titre" par élément, pour awk, grep ou une barre d'état;
+        // l'état est le nom stable du format CSV (Afaire, EnCours, EnAttente, Terminee)
+        ("status", rest) => {
+            let (flags, names): (Vec<&String>, Vec<&String>) = rest.iter().partition(|arg| *arg == "--incomplete-only");
+            let incomplete_only = !flags.is_empty();
+            let [list_name] = names.as_slice() else {
+                print_usage();
+                return Err(CliError::Usage);
+            };
+            let todo_list = load_cli_list(list_name)?;
+            for item in todo_list.items.iter().filter(|item| !incomplete_only || !item.status.is_done()) {
+                let title: String = item.title.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+                println!("{}\t{:?}\t{}", item.id, item.status, title);
+            }
+            Ok(())
+        }
+        ("review", []) => {
+            print!("{}", daily_review(Utc::now()));
+            Ok(())
+        }
+        // commande cachée pour les scripts de complétion du shell: un nom de fichier par ligne
+        ("__list-names", []) => {
+            for name in list_saved_todo_lists() {
+                println!("{}", sanitize_name(&name));
+            }
+            Ok(())
+        }
+        // --regex et --case-sensitive peuvent se placer avant ou après le terme
+        ("search", rest) => {
+            let mut options = SearchOptions::default();
+            let mut terms = Vec::new();
+            for arg in rest {
+                match arg.as_str() {
+                    "--regex" => options.regex = true,
+                    "--case-sensitive" => options.case_sensitive = true,
+                    _ => terms.push(arg),
+                }
+            }
+            let [query] = terms.as_slice() else {
+                print_usage();
+                return Err(CliError::Usage);
+            };
+            let results = search_all(query, options).map_err(|e| {
+                eprintln!("Expression régulière refusée: {}", e);
+                CliError::Usage
+            })?;
+            if json {
+                let results: Vec<_> = results
+                    .iter()
+                    .map(|(list_name, item)| serde_json::json!({ "list": list_name, "item": item }))
+                    .collect();
+                print_json(&results);
+            } else {
+                print_search_results(query, &results);
+            }
+            Ok(())
+        }
+        // déplace les listes existantes; le choix du stockage reste à faire dans config.json
+        ("storage", [backend]) => {
+            let (to, value) = match backend.as_str() {
+                "combined" => (StorageBackend::Combined, "combined"),
+                "files" => (StorageBackend::Files, "files"),
+                _ => {
+                    print_usage();
+                    return Err(CliError::Usage);
+                }
+            };
+            let _locks = list_saved_todo_lists().iter().map(|list_name| lock_cli_list(list_name)).collect::<Result<Vec<_>, _>>()?;
+            match convert_storage(to) {
+                Ok(count) => {
+                    if verbosity.shows_success() {
+                        println!("{} liste(s) déplacée(s).", count);
+                    }
+                    if config().storage != to {
+                        println!("Ajoutez \"storage\": \"{}\" dans {} pour utiliser ce stockage.", value, data_dir().join("config.json").display());
+                    }
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("Erreur lors de la conversion: {}", e);
+                    Err(CliError::Save)
+                }
+            }
+        }
+        _ => {
+            print_usage();
+            Err(CliError::Usage)
+        }
+    }
+}
+
+// retrouve une liste sans tenir compte de la casse: nom exact, sinon un seul nom qui
+// commence par la saisie, sinon un seul nom qui la contient ("groc" -> "groceries")
+fn resolve_list_name(input: &str) -> Option<String> {
+    let wanted = sanitize_name(input);
+    let lists = if wanted.is_empty() { Vec::new() } else { list_saved_todo_lists() };
+    if let Some(exact) = lists.iter().find(|name| sanitize_name(name) == wanted) {
+        return Some(exact.clone());
+    }
+
+    let prefix: Vec<&String> = lists.iter().filter(|name| sanitize_name(name).starts_with(&wanted)).collect();
+    let candidates = if prefix.is_empty() {
+        lists.iter().filter(|name| sanitize_name(name).contains(&wanted)).collect()
+    } else {
+        prefix
+    };
+    match candidates.as_slice() {
+        [] => {
+            eprintln!("Aucune liste ne correspond à '{}'.", input);
+            None
+        }
+        [name] => Some(name.to_string()),
+        _ => {
+            eprintln!("Plusieurs listes correspondent à '{}':", input);
+            for name in candidates {
+                eprintln!("  {}", name);
+            }
+            None
+        }
+    }
+}
+
+fn load_cli_list(input: &str) -> Result<TodoList, CliError> {
+    let list_name = resolve_list_name(input).ok_or(CliError::ListNotFound)?;
+    load_todo_list(&list_name).map_err(|e| {
+        eprintln!("Erreur lors du chargement de '{}': {}", list_name, e);
+        CliError::ListNotFound
+    })
+}
+
+// un script ne passe jamais outre un verrou: la liste est peut-être ouverte dans le menu
+fn lock_cli_list(list_name: &str) -> Result<ListLock, CliError> {
+    lock_list(list_name, false).map_err(|e| {
+        eprintln!("Impossible de modifier '{}': {}.", list_name, e);
+        CliError::Locked
+    })
+}
+
+// comme load_cli_list, pour les commandes qui modifient la liste: le verrou est tenu jusqu'à la sauvegarde
+fn load_locked_cli_list(input: &str) -> Result<(ListLock, TodoList), CliError> {
+    let list_name = resolve_list_name(input).ok_or(CliError::ListNotFound)?;
+    let lock = lock_cli_list(&list_name)?;
+    let todo_list = load_todo_list(&list_name).map_err(|e| {
+        eprintln!("Erreur lors du chargement de '{}': {}", list_name, e);
+        CliError::ListNotFound
+    })?;
+    Ok((lock, todo_list))
+}
+
+fn save_cli_list(todo_list: &mut TodoList, dry_run: bool, verbosity: Verbosity) -> Result<(), CliError> {
+    if dry_run {
+        println!("[simulation] Aucun fichier modifié.");
+        return Ok(());
+    }
+    save_list(todo_list, verbosity).map_err(|e| {
+        eprintln!("Erreur lors de la sauvegarde: {}", e);
+        CliError::Save
+    })
+}
+
+// même mise en forme que les fichiers sauvegardés
+fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Erreur lors de la conversion en JSON: {}", e),
     }
 }
+
+fn print_usage() {
+    println!("Utilisation:");
+    println!("  todocli                                    Lance le menu interactif");
+    println!("  todocli add <titre>                        Ajoute un élément à la liste par défaut (inbox)");
+    println!("  todocli add <liste> <titre> [description]  Ajoute un élément à une liste");
+    println!("  todocli done <liste> <id|titre> [--force]  Marque un élément comme terminé/non terminé");
+    println!("  todocli open <n>                           Modifie la n-ième liste (ordre alphabétique)");
+    println!("  todocli list <liste>                       Affiche une liste");
+    println!("  todocli status <liste> [--incomplete-only] Une ligne id<TAB>état<TAB>titre par élément");
+    println!("  todocli review                             Bilan du jour: en retard, à faire aujourd'hui, terminé aujourd'hui");
+    println!("  todocli delete <liste>                     Déplace une liste dans la corbeille");
+    println!("  todocli clear <liste>                      Supprime les éléments terminés d'une liste");
+    println!("  todocli archive <liste>                    Archive les éléments terminés d'une liste");
+    println!("  todocli search <terme> [--regex] [--case-sensitive]");
+    println!("                                             Recherche une tâche dans toutes les listes");
+    println!("                                             --regex: . ^ $ * + ? | ( ) [a-z] [^...] \\d \\w \\s;");
+    println!("                                             pas de {{n,m}}, \\b, \\p, ni de références arrière \\1");
+    println!("  todocli storage <combined|files>           Regroupe les listes dans todos.json ou les sépare en un fichier chacune");
+    println!();
+    println!("Options:");
+    println!("  --json                                     Sortie JSON pour list et search");
+    println!("  --dry-run                                  Avec delete, clear et archive: affiche l'effet sans rien modifier");
+    println!("  -q, --quiet                                N'affiche pas les messages de confirmation");
+    println!("  -v, --verbose                              Affiche des détails (fichiers écrits, éléments concernés)");
+    println!();
+    println!("Codes de sortie:");
+    println!("  0 succès, 1 commande invalide, 2 liste introuvable, 3 élément introuvable, 4 échec de la sauvegarde, 5 erreur de lecture, 6 sous-tâches non terminées, 7 liste verrouillée");
+}
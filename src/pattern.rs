@@ -0,0 +1,351 @@
+// expressions régulières simples pour la recherche, sans dépendance externe
+// syntaxe reconnue: . ^ $ * + ? | ( ) [abc] [a-z] [^...] \d \w \s (\D \W \S) et \ pour échapper
+// refusée plutôt que prise pour du texte: {n,m}, \b, \B, \p, \P et les références arrière \1 à \9
+// le retour arrière peut être exponentiel ((a*)*b...): chaque texte examiné a un budget d'étapes
+
+use std::cell::Cell;
+use std::fmt;
+
+// au-delà, le motif est refusé avant même la recherche
+pub const MAX_PATTERN_LEN: usize = 256;
+// étapes de retour arrière autorisées pour un texte, largement assez pour un motif raisonnable
+pub const MAX_STEPS: usize = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternError {
+    UnclosedGroup(usize),
+    UnmatchedParen(usize),
+    UnclosedClass(usize),
+    InvalidRange(char, char),
+    NothingToRepeat(char, usize),
+    TrailingBackslash,
+    // syntaxe courante ailleurs mais pas ici, avec sa position
+    Unsupported(String, usize),
+    TooLong(usize),
+    TooComplex(usize),
+}
+
+// les positions sont comptées à partir de 1, comme on lit le motif
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::UnclosedGroup(pos) => write!(f, "la parenthèse ouverte en position {} n'est jamais fermée", pos),
+            PatternError::UnmatchedParen(pos) => write!(f, "parenthèse fermante sans ouverture en position {}", pos),
+            PatternError::UnclosedClass(pos) => write!(f, "le crochet ouvert en position {} n'est jamais fermé", pos),
+            PatternError::InvalidRange(start, end) => write!(f, "intervalle invalide '{}-{}' (début après la fin)", start, end),
+            PatternError::NothingToRepeat(c, pos) => write!(f, "'{}' en position {} ne suit rien à répéter", c, pos),
+            PatternError::TrailingBackslash => write!(f, "'\\' final sans caractère à échapper"),
+            PatternError::Unsupported(syntax, pos) => write!(f, "'{}' en position {} n'est pas pris en charge", syntax, pos),
+            PatternError::TooLong(max) => write!(f, "motif trop long ({} caractères au maximum)", max),
+            PatternError::TooComplex(max) => {
+                write!(f, "motif trop complexe: plus de {} étapes sur un même texte; évitez les répétitions imbriquées comme (a*)*", max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[derive(Debug, Clone)]
+enum ClassItem {
+    Range(char, char),
+    // \d, \w, \s et leurs négations \D, \W, \S
+    Shorthand(char),
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Char(char),
+    Any,
+    Class { items: Vec<ClassItem>, negated: bool },
+    Start,
+    End,
+    Alt(Vec<Vec<Node>>),
+    Repeat { node: Box<Node>, min: usize, max: Option<usize> },
+}
+
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    nodes: Vec<Node>,
+    case_sensitive: bool,
+}
+
+impl Pattern {
+    pub fn new(pattern: &str, case_sensitive: bool) -> Result<Pattern, PatternError> {
+        if pattern.chars().count() > MAX_PATTERN_LEN {
+            return Err(PatternError::TooLong(MAX_PATTERN_LEN));
+        }
+        let mut parser = Parser { chars: pattern.chars().collect(), pos: 0 };
+        let alternatives = parser.parse_alternatives()?;
+        if parser.pos < parser.chars.len() {
+            return Err(PatternError::UnmatchedParen(parser.pos + 1));
+        }
+        Ok(Pattern { nodes: vec![Node::Alt(alternatives)], case_sensitive })
+    }
+
+    // le texte tel quel, sans caractère spécial
+    pub fn literal(text: &str, case_sensitive: bool) -> Pattern {
+        Pattern { nodes: text.chars().map(Node::Char).collect(), case_sensitive }
+    }
+
+    // vrai si le motif apparaît quelque part dans le texte; TooComplex si le budget d'étapes est épuisé
+    pub fn is_match(&self, text: &str) -> Result<bool, PatternError> {
+        let text: Vec<char> = text.chars().collect();
+        let matcher = Matcher { text: &text, case_sensitive: self.case_sensitive, steps: Cell::new(0) };
+        let found = (0..=text.len()).any(|start| matcher.match_seq(&self.nodes, start, &mut |_| true));
+        if matcher.steps.get() > MAX_STEPS {
+            return Err(PatternError::TooComplex(MAX_STEPS));
+        }
+        Ok(found)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn parse_alternatives(&mut self) -> Result<Vec<Vec<Node>>, PatternError> {
+        let mut alternatives = vec![self.parse_sequence()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            alternatives.push(self.parse_sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn parse_sequence(&mut self) -> Result<Vec<Node>, PatternError> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let mut node = self.parse_atom()?;
+            while let Some(quantifier) = self.peek() {
+                let (min, max) = match quantifier {
+                    '*' => (0, None),
+                    '+' => (1, None),
+                    '?' => (0, Some(1)),
+                    _ => break,
+                };
+                self.pos += 1;
+                node = Node::Repeat { node: Box::new(node), min, max };
+            }
+            nodes.push(node);
+        }
+        Ok(nodes)
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, PatternError> {
+        let start = self.pos;
+        let c = self.chars[start];
+        self.pos += 1;
+        match c {
+            '(' => {
+                let alternatives = self.parse_alternatives()?;
+                if self.peek() != Some(')') {
+                    return Err(PatternError::UnclosedGroup(start + 1));
+                }
+                self.pos += 1;
+                Ok(Node::Alt(alternatives))
+            }
+            '[' => self.parse_class(start),
+            '*' | '+' | '?' => Err(PatternError::NothingToRepeat(c, start + 1)),
+            '{' if self.peek().is_some_and(|next| next.is_ascii_digit()) => Err(PatternError::Unsupported("{n,m}".to_string(), start + 1)),
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::Start),
+            '$' => Ok(Node::End),
+            '\\' => match self.parse_escape()? {
+                ClassItem::Range(c, _) => Ok(Node::Char(c)),
+                shorthand => Ok(Node::Class { items: vec![shorthand], negated: false }),
+            },
+            _ => Ok(Node::Char(c)),
+        }
+    }
+
+    // appelé juste après le '\'
+    fn parse_escape(&mut self) -> Result<ClassItem, PatternError> {
+        let c = self.peek().ok_or(PatternError::TrailingBackslash)?;
+        if matches!(c, 'b' | 'B' | 'p' | 'P' | '1'..='9') {
+            return Err(PatternError::Unsupported(format!("\\{}", c), self.pos));
+        }
+        self.pos += 1;
+        Ok(match c {
+            'd' | 'w' | 's' | 'D' | 'W' | 'S' => ClassItem::Shorthand(c),
+            _ => ClassItem::Range(c, c),
+        })
+    }
+
+    // un ']' juste après '[' (ou '[^') est un caractère ordinaire, comme un '-' en fin de classe
+    fn parse_class(&mut self, start: usize) -> Result<Node, PatternError> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            let c = self.peek().ok_or(PatternError::UnclosedClass(start + 1))?;
+            self.pos += 1;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let item = if c == '\\' { self.parse_escape()? } else { ClassItem::Range(c, c) };
+            let ClassItem::Range(low, _) = item else {
+                items.push(item);
+                continue;
+            };
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&next| next != ']') {
+                let high = self.chars[self.pos + 1];
+                self.pos += 2;
+                if low > high {
+                    return Err(PatternError::InvalidRange(low, high));
+                }
+                items.push(ClassItem::Range(low, high));
+            } else {
+                items.push(item);
+            }
+        }
+        Ok(Node::Class { items, negated })
+    }
+}
+
+// recherche par retour arrière: chaque nœud appelle la suite `next` avec la position atteinte
+struct Matcher<'a> {
+    text: &'a [char],
+    case_sensitive: bool,
+    // une fois MAX_STEPS dépassé, plus rien ne correspond et la recherche se termine vite
+    steps: Cell<usize>,
+}
+
+impl Matcher<'_> {
+    fn match_seq(&self, nodes: &[Node], pos: usize, next: &mut dyn FnMut(usize) -> bool) -> bool {
+        match nodes.split_first() {
+            None => next(pos),
+            Some((node, rest)) => self.match_node(node, pos, &mut |after| self.match_seq(rest, after, next)),
+        }
+    }
+
+    fn match_node(&self, node: &Node, pos: usize, next: &mut dyn FnMut(usize) -> bool) -> bool {
+        self.steps.set(self.steps.get() + 1);
+        if self.steps.get() > MAX_STEPS {
+            return false;
+        }
+        match node {
+            Node::Start => pos == 0 && next(pos),
+            Node::End => pos == self.text.len() && next(pos),
+            Node::Alt(alternatives) => alternatives.iter().any(|sequence| self.match_seq(sequence, pos, next)),
+            Node::Repeat { node, min, max } => self.match_repeat(node, *min, *max, 0, pos, next),
+            _ => match self.text.get(pos) {
+                Some(&c) if self.matches_char(node, c) => next(pos + 1),
+                _ => false,
+            },
+        }
+    }
+
+    // gourmand: on tente une répétition de plus avant de passer à la suite
+    fn match_repeat(
+        &self,
+        node: &Node,
+        min: usize,
+        max: Option<usize>,
+        count: usize,
+        pos: usize,
+        next: &mut dyn FnMut(usize) -> bool,
+    ) -> bool {
+        if max.is_none_or(|max| count < max)
+            && self.match_node(node, pos, &mut |after| after != pos && self.match_repeat(node, min, max, count + 1, after, next))
+        {
+            return true;
+        }
+        count >= min && next(pos)
+    }
+
+    fn matches_char(&self, node: &Node, c: char) -> bool {
+        if self.case_sensitive {
+            return Self::matches_exact(node, c);
+        }
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        let upper = c.to_uppercase().next().unwrap_or(c);
+        Self::matches_exact(node, c) || Self::matches_exact(node, lower) || Self::matches_exact(node, upper)
+    }
+
+    fn matches_exact(node: &Node, c: char) -> bool {
+        match node {
+            Node::Char(expected) => *expected == c,
+            Node::Any => true,
+            Node::Class { items, negated } => {
+                let found = items.iter().any(|item| match item {
+                    ClassItem::Range(low, high) => (*low..=*high).contains(&c),
+                    ClassItem::Shorthand(kind) => {
+                        let matched = match kind.to_ascii_lowercase() {
+                            'd' => c.is_ascii_digit(),
+                            'w' => c.is_alphanumeric() || c == '_',
+                            _ => c.is_whitespace(),
+                        };
+                        matched != kind.is_ascii_uppercase()
+                    }
+                });
+                found != *negated
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regex_syntax_matches() {
+        let matches = |pattern: &str, text: &str| Pattern::new(pattern, false).expect("motif valide").is_match(text) == Ok(true);
+        assert!(matches("^rapport (mensuel|annuel)$", "Rapport annuel"));
+        assert!(!matches("^rapport (mensuel|annuel)$", "rapport trimestriel"));
+        assert!(matches(r"facture n°\d+", "payer facture n°1234"));
+        assert!(!matches(r"facture n°\d+", "payer facture n°"));
+        assert!(matches("[a-c]x?y", "zzby"));
+        assert!(matches("[^0-9]+$", "abc"));
+        assert!(!matches("^[^0-9]+$", "ab1"));
+        assert!(matches("ét[eé]", "ÉTÉ"));
+        assert_eq!(Pattern::new("Été", true).expect("motif valide").is_match("été"), Ok(false));
+        // les caractères spéciaux d'une recherche littérale ne sont pas interprétés
+        assert_eq!(Pattern::literal("a.b", false).is_match("A.B"), Ok(true));
+        assert_eq!(Pattern::literal("a.b", false).is_match("axb"), Ok(false));
+        assert!(matches("prix {total}", "prix {total}"));
+    }
+
+    #[test]
+    fn invalid_patterns_are_reported() {
+        assert_eq!(Pattern::new("(ab", false).unwrap_err(), PatternError::UnclosedGroup(1));
+        assert_eq!(Pattern::new("ab)", false).unwrap_err(), PatternError::UnmatchedParen(3));
+        assert_eq!(Pattern::new("x[ab", false).unwrap_err(), PatternError::UnclosedClass(2));
+        assert_eq!(Pattern::new("*a", false).unwrap_err(), PatternError::NothingToRepeat('*', 1));
+        assert_eq!(Pattern::new("[z-a]", false).unwrap_err(), PatternError::InvalidRange('z', 'a'));
+        assert_eq!(Pattern::new("a\\", false).unwrap_err(), PatternError::TrailingBackslash);
+        assert_eq!(Pattern::new("a{2,3}", false).unwrap_err(), PatternError::Unsupported("{n,m}".to_string(), 2));
+        assert_eq!(Pattern::new(r"\bmot", false).unwrap_err(), PatternError::Unsupported(r"\b".to_string(), 1));
+        assert_eq!(Pattern::new(r"(a)[x\1]", false).unwrap_err(), PatternError::Unsupported(r"\1".to_string(), 6));
+    }
+
+    #[test]
+    fn pattern_size_and_backtracking_are_bounded() {
+        let longest = "a".repeat(MAX_PATTERN_LEN);
+        assert!(Pattern::new(&longest, false).is_ok());
+        assert_eq!(Pattern::new(&format!("{}a", longest), false).unwrap_err(), PatternError::TooLong(MAX_PATTERN_LEN));
+
+        // répétitions imbriquées: exponentiel sans budget, refusé avec une erreur claire
+        let pattern = Pattern::new("(a*)*b", false).expect("motif valide");
+        assert_eq!(pattern.is_match(&"a".repeat(40)), Err(PatternError::TooComplex(MAX_STEPS)));
+        assert_eq!(pattern.is_match("aaab"), Ok(true));
+        // un motif ordinaire reste sous le budget sur un long texte
+        let description = "note ".repeat(2_000) + "facture n°42";
+        assert_eq!(Pattern::new(r"facture n°\d+$", false).expect("motif valide").is_match(&description), Ok(true));
+    }
+}